@@ -1,10 +1,15 @@
-pub fn main() {
-    intro();
-    format_print();
-    debug();
-    display();
-    formatting();
-}
+#![allow(unused_imports)]
+#![allow(clippy::excessive_precision, clippy::format_in_format_args, clippy::print_literal)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("intro", "Hello World", intro),
+    Section::new("format_print", "Formatted print", format_print),
+    Section::new("debug", "Debug", debug),
+    Section::new("display", "Display", display),
+    Section::new("formatting", "Formatting", formatting),
+];
 
 fn intro() {
     println!("Hello world!");
@@ -12,7 +17,7 @@ fn intro() {
 }
 
 fn format_print() {
-    eprintln!("Error print");
+    error!("Error print");
 
     print!("{} Month is ", 1);
     println!("{}", format!("{} days", 31));
@@ -51,9 +56,9 @@ fn format_print() {
 }
 
 #[derive(Debug)]
-struct Person<'a> {
-    name: &'a str,
-    age: u8
+pub struct Person<'a> {
+    pub name: &'a str,
+    pub age: u8
 }
 
 fn debug() {
@@ -70,9 +75,11 @@ fn debug() {
 
 use std::fmt::{self, Formatter, Display, write};
 
-struct Point2D {
-    x: f64,
-    y: f64,
+use crate::list::List;
+
+pub struct Point2D {
+    pub x: f64,
+    pub y: f64,
 }
 
 impl Display for Point2D {
@@ -81,18 +88,11 @@ impl Display for Point2D {
     }
 }
 
-struct MyList(Vec<i32>);
+pub struct MyList(pub Vec<i32>);
 
 impl Display for MyList {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let vec = &self.0;
-
-        write!(f, "[")?;
-        for (count, v) in vec.iter().enumerate() {
-            if count != 0 { write!(f, ", ")?; }
-            write!(f, "{}: {}", count, v)?;
-        }
-        write!(f, "]")
+        write!(f, "{}", List::new(&self.0).indexed())
     }
 }
 
@@ -106,10 +106,10 @@ fn display() {
     // [0: 1, 1: 2, 2: 3]
 }
 
-struct City {
-    name: &'static str,
-    lat: f32,
-    lon: f32,
+pub struct City {
+    pub name: &'static str,
+    pub lat: f32,
+    pub lon: f32,
 }
 
 impl Display for City {
@@ -122,10 +122,10 @@ impl Display for City {
     }
 }
 
-struct Color {
-    red: u8,
-    green: u8,
-    blue: u8,
+pub struct Color {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
 }
 
 impl Display for Color {
@@ -1,9 +1,14 @@
-pub fn main() {
-    types();
-    literals_and_operators();
-    tuples();
-    arrays_and_slices();
-}
+#![allow(unused_assignments, unused_variables)]
+#![allow(clippy::nonminimal_bool)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("types", "Primitives", types),
+    Section::new("literals_and_operators", "Literals and operators", literals_and_operators),
+    Section::new("tuples", "Tuples", tuples),
+    Section::new("arrays_and_slices", "Arrays and Slices", arrays_and_slices),
+];
 
 fn types() {
     let logical: bool = true;
@@ -0,0 +1,615 @@
+use std::fmt;
+use std::str::FromStr;
+
+/*
+Integers of every primitive width for the examples of
+`b_primitives::literals_and_operators`: converting between bases 2 to 36,
+showing the two's-complement bits the way `{:08b}` would with the digits
+grouped, and evaluating expressions like `0b0011u32 & 0b0101` or `1 << 5`
+with the precedence of Rust. Arithmetic that would panic in a debug build,
+like `255u8 + 1`, `1u32 << 32` or `5 / 0`, is reported instead with the
+message of that panic.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum IntType {
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Usize,
+    Isize,
+}
+
+pub const INT_TYPES: [IntType; 12] = [
+    IntType::U8,
+    IntType::U16,
+    IntType::U32,
+    IntType::U64,
+    IntType::U128,
+    IntType::I8,
+    IntType::I16,
+    IntType::I32,
+    IntType::I64,
+    IntType::I128,
+    IntType::Usize,
+    IntType::Isize,
+];
+
+impl IntType {
+    pub fn name(self) -> &'static str {
+        match self {
+            IntType::U8 => "u8",
+            IntType::U16 => "u16",
+            IntType::U32 => "u32",
+            IntType::U64 => "u64",
+            IntType::U128 => "u128",
+            IntType::I8 => "i8",
+            IntType::I16 => "i16",
+            IntType::I32 => "i32",
+            IntType::I64 => "i64",
+            IntType::I128 => "i128",
+            IntType::Usize => "usize",
+            IntType::Isize => "isize",
+        }
+    }
+
+    pub fn bits(self) -> u32 {
+        match self {
+            IntType::U8 | IntType::I8 => 8,
+            IntType::U16 | IntType::I16 => 16,
+            IntType::U32 | IntType::I32 => 32,
+            IntType::U64 | IntType::I64 => 64,
+            IntType::U128 | IntType::I128 => 128,
+            IntType::Usize | IntType::Isize => usize::BITS,
+        }
+    }
+
+    pub fn signed(self) -> bool {
+        matches!(self, IntType::I8 | IntType::I16 | IntType::I32 | IntType::I64 | IntType::I128 | IntType::Isize)
+    }
+
+    fn mask(self) -> u128 {
+        u128::MAX >> (128 - self.bits())
+    }
+}
+
+impl fmt::Display for IntType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for IntType {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<IntType, Error> {
+        INT_TYPES.into_iter()
+            .find(|int_type| int_type.name() == name)
+            .ok_or_else(|| Error::Type(format!("unknown integer type `{}`", name)))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Syntax { column: usize, message: String },
+    Radix(u32),
+    Type(String),
+    OutOfRange { literal: String, int_type: IntType },
+    /*
+    What a debug build panics with, like `attempt to add with overflow`.
+     */
+    Arithmetic { expression: String, int_type: IntType, message: &'static str },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax { column, message } => write!(f, "column {}: {}", column, message),
+            Error::Radix(radix) => write!(f, "radix {} is out of range, expected 2 to 36", radix),
+            Error::Type(message) => write!(f, "{}", message),
+            Error::OutOfRange { literal, int_type } => write!(f, "`{}` is out of range for `{}`", literal, int_type),
+            Error::Arithmetic { expression, int_type, message } => write!(f, "`{}` in `{}`: {}", expression, int_type, message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/*
+An integer of one of the primitive types, kept as its two's-complement bits
+in the low bits of a `u128`.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Int {
+    int_type: IntType,
+    bits: u128,
+}
+
+macro_rules! int_from {
+    ($($primitive:ty => $int_type:ident,)*) => {
+        $(
+            impl From<$primitive> for Int {
+                fn from(value: $primitive) -> Int {
+                    Int::from_bits(IntType::$int_type, value as u128)
+                }
+            }
+        )*
+    };
+}
+
+int_from! {
+    u8 => U8, u16 => U16, u32 => U32, u64 => U64, u128 => U128, usize => Usize,
+    i8 => I8, i16 => I16, i32 => I32, i64 => I64, i128 => I128, isize => Isize,
+}
+
+impl Int {
+    /*
+    Truncates `bits` to the width of the type, like `as` does.
+     */
+    pub fn from_bits(int_type: IntType, bits: u128) -> Int {
+        Int { int_type, bits: bits & int_type.mask() }
+    }
+
+    /*
+    `None` when the value does not fit in the type.
+     */
+    pub fn new(int_type: IntType, value: i128) -> Option<Int> {
+        let int = Int::from_bits(int_type, value as u128);
+        let fits = if int_type == IntType::U128 { value >= 0 } else { int.to_i128() == Some(value) };
+        fits.then_some(int)
+    }
+
+    pub fn int_type(&self) -> IntType {
+        self.int_type
+    }
+
+    pub fn bits(&self) -> u128 {
+        self.bits
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.int_type.signed() && self.bits >> (self.int_type.bits() - 1) & 1 == 1
+    }
+
+    /*
+    The value, which only a `u128` above `i128::MAX` does not fit.
+     */
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.is_negative() {
+            Some((self.bits | !self.int_type.mask()) as i128)
+        } else {
+            i128::try_from(self.bits).ok()
+        }
+    }
+
+    fn magnitude(&self) -> u128 {
+        if self.is_negative() {
+            (self.bits | !self.int_type.mask()).wrapping_neg()
+        } else {
+            self.bits
+        }
+    }
+
+    /*
+    The value in a base from 2 to 36 with lowercase digits and a `-` for
+    negative numbers, like `{:x}` would print the magnitude.
+     */
+    pub fn to_radix(&self, radix: u32) -> Result<String, Error> {
+        check_radix(radix)?;
+        let mut magnitude = self.magnitude();
+        let mut digits = Vec::new();
+        loop {
+            digits.push(std::char::from_digit((magnitude % radix as u128) as u32, radix).expect("a digit below the radix"));
+            magnitude /= radix as u128;
+            if magnitude == 0 {
+                break;
+            }
+        }
+        if self.is_negative() {
+            digits.push('-');
+        }
+        Ok(digits.iter().rev().collect())
+    }
+
+    /*
+    Parses digits in a base from 2 to 36, with an optional sign and `_`
+    between the digits, the reverse of `to_radix`.
+     */
+    pub fn from_radix(text: &str, radix: u32, int_type: IntType) -> Result<Int, Error> {
+        check_radix(radix)?;
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text.strip_prefix('+').unwrap_or(text)),
+        };
+        parse_digits(digits, radix, 1)?
+            .and_then(|magnitude| Int::from_magnitude(negative, magnitude, int_type))
+            .ok_or_else(|| Error::OutOfRange { literal: text.to_owned(), int_type })
+    }
+
+    /*
+    The value `magnitude` or `-magnitude`, or `None` when it does not fit.
+     */
+    pub fn from_magnitude(negative: bool, magnitude: u128, int_type: IntType) -> Option<Int> {
+        if !negative {
+            return (magnitude <= int_type.mask() >> int_type.signed() as u32).then(|| Int::from_bits(int_type, magnitude));
+        }
+        let limit = if int_type.signed() { 1u128 << (int_type.bits() - 1) } else { 0 };
+        (magnitude <= limit).then(|| Int::from_bits(int_type, magnitude.wrapping_neg()))
+    }
+
+    /*
+    The bits from the most significant one down, with a space between every
+    `group` of them counted from the right, or none when `group` is 0:
+    `-2i8` is `1111 1110` in groups of 4.
+     */
+    pub fn layout(&self, group: usize) -> String {
+        let width = self.int_type.bits() as usize;
+        let digits = format!("{:0width$b}", self.bits, width = width);
+        if group == 0 {
+            return digits;
+        }
+        let mut grouped = String::new();
+        for (index, digit) in digits.chars().enumerate() {
+            if index > 0 && (width - index).is_multiple_of(group) {
+                grouped.push(' ');
+            }
+            grouped.push(digit);
+        }
+        grouped
+    }
+
+    pub fn min(int_type: IntType) -> Int {
+        Int::from_bits(int_type, if int_type.signed() { 1 << (int_type.bits() - 1) } else { 0 })
+    }
+
+    pub fn max(int_type: IntType) -> Int {
+        Int::from_bits(int_type, int_type.mask() >> int_type.signed() as u32)
+    }
+
+    pub fn convert(&self, int_type: IntType) -> Option<Int> {
+        match self.to_i128() {
+            Some(value) => Int::new(int_type, value),
+            None => (int_type == IntType::U128).then_some(*self),
+        }
+    }
+}
+
+impl fmt::Display for Int {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad_integral(!self.is_negative(), "", &self.magnitude().to_string())
+    }
+}
+
+fn check_radix(radix: u32) -> Result<(), Error> {
+    if (2..=36).contains(&radix) { Ok(()) } else { Err(Error::Radix(radix)) }
+}
+
+/*
+The digits of an unsigned number with `_` allowed between them, or `None`
+when they do not fit in 128 bits. `column` is where the digits start, for
+the errors.
+ */
+pub fn parse_digits(digits: &str, radix: u32, column: usize) -> Result<Option<u128>, Error> {
+    let mut value: Option<u128> = Some(0);
+    let mut any = false;
+    for (index, c) in digits.chars().enumerate() {
+        if c == '_' {
+            continue;
+        }
+        let digit = c.to_digit(radix).ok_or_else(|| Error::Syntax {
+            column: column + index,
+            message: format!("`{}` is not a digit in base {}", c, radix),
+        })?;
+        value = value.and_then(|value| value.checked_mul(radix as u128)?.checked_add(digit as u128));
+        any = true;
+    }
+    if !any {
+        return Err(Error::Syntax { column, message: "expected digits".to_owned() });
+    }
+    Ok(value)
+}
+
+/*
+Evaluates integer literals in any base, with an optional type suffix, and
+the operators `!` and `-` (unary), `*`, `/`, `%`, `+`, `-`, `<<`, `>>`, `&`,
+`^` and `|` from the tightest binding to the loosest, with parentheses.
+Every literal has the same type: the one of the suffixes, which must agree,
+or else `int_type`, or else `i32` like an unsuffixed literal in Rust.
+ */
+pub fn eval(expression: &str, int_type: Option<IntType>) -> Result<Int, Error> {
+    let tokens = tokenize(expression)?;
+    let mut suffixed: Option<IntType> = None;
+    for token in &tokens {
+        if let TokenKind::Number { suffix: Some(suffix), .. } = token.kind {
+            match suffixed.or(int_type) {
+                Some(expected) if expected != suffix => {
+                    return Err(Error::Type(format!("mismatched types: expected `{}`, found `{}`", expected, suffix)));
+                }
+                _ => suffixed = Some(suffix),
+            }
+        }
+    }
+
+    let mut evaluator = Evaluator { tokens, position: 0, depth: 0, int_type: suffixed.or(int_type).unwrap_or(IntType::I32) };
+    let value = evaluator.binary(0)?;
+    match &evaluator.peek().kind {
+        TokenKind::End => Ok(value),
+        _ => Err(evaluator.unexpected()),
+    }
+}
+
+struct Token {
+    kind: TokenKind,
+    column: usize,
+}
+
+enum TokenKind {
+    Number { digits: String, radix: u32, suffix: Option<IntType> },
+    Operator(&'static str),
+    Open,
+    Close,
+    End,
+}
+
+const OPERATORS: [&str; 12] = ["<<", ">>", "+", "-", "*", "/", "%", "&", "|", "^", "!", "~"];
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, Error> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut position = 0;
+
+    while position < chars.len() {
+        let c = chars[position];
+        let column = position + 1;
+        if c.is_whitespace() {
+            position += 1;
+            continue;
+        }
+        let kind = if c.is_ascii_digit() {
+            let start = position;
+            while position < chars.len() && (chars[position].is_ascii_alphanumeric() || chars[position] == '_') {
+                position += 1;
+            }
+            let word: String = chars[start..position].iter().collect();
+            tokens.push(Token { kind: number(&word, column)?, column });
+            continue;
+        } else if c == '(' {
+            TokenKind::Open
+        } else if c == ')' {
+            TokenKind::Close
+        } else {
+            let rest: String = chars[position..chars.len().min(position + 2)].iter().collect();
+            match OPERATORS.into_iter().find(|operator| rest.starts_with(operator)) {
+                Some("~") => return Err(Error::Syntax { column, message: "`~` is not an operator, use `!` to flip the bits".to_owned() }),
+                Some(operator) => {
+                    position += operator.len() - 1;
+                    TokenKind::Operator(operator)
+                }
+                None => return Err(Error::Syntax { column, message: format!("unexpected `{}`", c) }),
+            }
+        };
+        position += 1;
+        tokens.push(Token { kind, column });
+    }
+    tokens.push(Token { kind: TokenKind::End, column: chars.len() + 1 });
+    Ok(tokens)
+}
+
+/*
+A literal like `0b0011`, `1_000_000u32` or `0x80`, split into its digits,
+radix and suffix.
+ */
+fn number(word: &str, column: usize) -> Result<TokenKind, Error> {
+    let (radix, rest) = match word.get(..2) {
+        Some("0b") => (2, &word[2..]),
+        Some("0o") => (8, &word[2..]),
+        Some("0x") => (16, &word[2..]),
+        _ => (10, word),
+    };
+    let (digits, suffix) = match rest.find(['u', 'i']) {
+        Some(index) => {
+            let suffix = rest[index..].parse::<IntType>().map_err(|_| Error::Syntax {
+                column: column + word.len() - rest.len() + index,
+                message: format!("invalid suffix `{}` for number literal", &rest[index..]),
+            })?;
+            (&rest[..index], Some(suffix))
+        }
+        None => (rest, None),
+    };
+    let start = column + word.len() - rest.len();
+    parse_digits(digits, radix, start)?;
+    Ok(TokenKind::Number { digits: digits.to_owned(), radix, suffix })
+}
+
+struct Evaluator {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+    int_type: IntType,
+}
+
+/*
+How deep parentheses and unary operators may nest in an expression. The
+evaluator recurses once per level, so a deeper one is an error rather than
+a stack overflow.
+ */
+pub const MAX_DEPTH: usize = 128;
+
+const PRECEDENCE: [&[&str]; 6] = [&["|"], &["^"], &["&"], &["<<", ">>"], &["+", "-"], &["*", "/", "%"]];
+
+impl Evaluator {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position]
+    }
+
+    fn unexpected(&self) -> Error {
+        let token = self.peek();
+        let message = match &token.kind {
+            TokenKind::End => "unexpected end of the expression".to_owned(),
+            TokenKind::Close => "unexpected `)`".to_owned(),
+            TokenKind::Operator(operator) => format!("unexpected `{}`", operator),
+            TokenKind::Number { .. } | TokenKind::Open => "expected an operator".to_owned(),
+        };
+        Error::Syntax { column: token.column, message }
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Int, Error> {
+        if level == PRECEDENCE.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        while let TokenKind::Operator(operator) = self.peek().kind {
+            if !PRECEDENCE[level].contains(&operator) {
+                break;
+            }
+            self.position += 1;
+            let right = self.binary(level + 1)?;
+            left = apply(operator, left, right)?;
+        }
+        Ok(left)
+    }
+
+    /*
+    Evaluates what follows the operator or the parenthesis at `column` one
+    level deeper.
+     */
+    fn nested(&mut self, column: usize, evaluate: impl FnOnce(&mut Evaluator) -> Result<Int, Error>) -> Result<Int, Error> {
+        if self.depth == MAX_DEPTH {
+            return Err(Error::Syntax { column, message: "nested too deeply".to_owned() });
+        }
+        self.depth += 1;
+        let value = evaluate(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn unary(&mut self) -> Result<Int, Error> {
+        let token = &self.tokens[self.position];
+        let column = token.column;
+        match &token.kind {
+            TokenKind::Operator("!") => {
+                self.position += 1;
+                let value = self.nested(column, Evaluator::unary)?;
+                Ok(Int::from_bits(value.int_type, !value.bits))
+            }
+            TokenKind::Operator("-") => {
+                self.position += 1;
+                if let TokenKind::Number { digits, radix, .. } = &self.peek().kind {
+                    let literal = format!("-{}", digits);
+                    let value = self.literal(true, digits.clone(), *radix, literal)?;
+                    self.position += 1;
+                    return Ok(value);
+                }
+                let value = self.nested(column, Evaluator::unary)?;
+                negate(value)
+            }
+            TokenKind::Number { digits, radix, .. } => {
+                let value = self.literal(false, digits.clone(), *radix, digits.clone())?;
+                self.position += 1;
+                Ok(value)
+            }
+            TokenKind::Open => {
+                self.position += 1;
+                let value = self.nested(column, |evaluator| evaluator.binary(0))?;
+                match self.peek().kind {
+                    TokenKind::Close => {
+                        self.position += 1;
+                        Ok(value)
+                    }
+                    _ => Err(self.unexpected()),
+                }
+            }
+            _ => Err(self.unexpected()),
+        }
+    }
+
+    fn literal(&self, negative: bool, digits: String, radix: u32, literal: String) -> Result<Int, Error> {
+        let int_type = self.int_type;
+        if negative && !int_type.signed() {
+            return Err(Error::Type(format!("cannot apply unary operator `-` to type `{}`", int_type)));
+        }
+        parse_digits(&digits, radix, 1)?
+            .and_then(|magnitude| Int::from_magnitude(negative, magnitude, int_type))
+            .ok_or(Error::OutOfRange { literal, int_type })
+    }
+}
+
+fn negate(value: Int) -> Result<Int, Error> {
+    let int_type = value.int_type;
+    if !int_type.signed() {
+        return Err(Error::Type(format!("cannot apply unary operator `-` to type `{}`", int_type)));
+    }
+    match value.to_i128().and_then(i128::checked_neg).and_then(|negated| Int::new(int_type, negated)) {
+        Some(negated) => Ok(negated),
+        None => Err(Error::Arithmetic { expression: format!("-({})", value), int_type, message: "attempt to negate with overflow" }),
+    }
+}
+
+/*
+The binary operators with the checks of a debug build: the result must fit
+the type and a shift must be by less than its number of bits.
+ */
+fn apply(operator: &str, left: Int, right: Int) -> Result<Int, Error> {
+    let int_type = left.int_type;
+    let failed = |message| Error::Arithmetic { expression: format!("{} {} {}", left, operator, right), int_type, message };
+
+    match operator {
+        "&" => return Ok(Int::from_bits(int_type, left.bits & right.bits)),
+        "|" => return Ok(Int::from_bits(int_type, left.bits | right.bits)),
+        "^" => return Ok(Int::from_bits(int_type, left.bits ^ right.bits)),
+        "<<" | ">>" => {
+            let shift = right.to_i128().filter(|&shift| (0..int_type.bits() as i128).contains(&shift));
+            let Some(shift) = shift else {
+                return Err(failed(if operator == "<<" { "attempt to shift left with overflow" } else { "attempt to shift right with overflow" }));
+            };
+            return Ok(match (operator, left.to_i128()) {
+                ("<<", _) => Int::from_bits(int_type, left.bits << shift),
+                (_, Some(value)) if int_type.signed() => Int::from_bits(int_type, (value >> shift) as u128),
+                _ => Int::from_bits(int_type, left.bits >> shift),
+            });
+        }
+        _ => {}
+    }
+
+    if right.bits == 0 && (operator == "/" || operator == "%") {
+        return Err(failed(if operator == "/" {
+            "attempt to divide by zero"
+        } else {
+            "attempt to calculate the remainder with a divisor of zero"
+        }));
+    }
+    let result = if int_type == IntType::U128 {
+        let (a, b) = (left.bits, right.bits);
+        match operator {
+            "+" => a.checked_add(b),
+            "-" => a.checked_sub(b),
+            "*" => a.checked_mul(b),
+            "/" => a.checked_div(b),
+            _ => a.checked_rem(b),
+        }
+        .map(|bits| Int::from_bits(int_type, bits))
+    } else {
+        let (a, b) = (left.to_i128().expect("fits in i128"), right.to_i128().expect("fits in i128"));
+        match operator {
+            "+" => a.checked_add(b),
+            "-" => a.checked_sub(b),
+            "*" => a.checked_mul(b),
+            "/" => a.checked_div(b),
+            _ => a.checked_rem(b),
+        }
+        .and_then(|value| Int::new(int_type, value))
+    };
+    result.ok_or_else(|| failed(match operator {
+        "+" => "attempt to add with overflow",
+        "-" => "attempt to subtract with overflow",
+        "*" => "attempt to multiply with overflow",
+        "/" => "attempt to divide with overflow",
+        _ => "attempt to calculate the remainder with overflow",
+    }))
+}
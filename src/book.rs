@@ -0,0 +1,240 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::chapter::Chapter;
+use crate::golden::Verdict;
+use crate::report::{escape_xml, ChapterReport, Report, SectionReport};
+use crate::source::{self, Line};
+
+/*
+Renders a report as a static book: an index page and one page per chapter.
+Every section shows the prose of its block comments, the code around them,
+including the types and helpers defined before the section, and the output
+it actually printed when the report was collected.
+ */
+#[derive(Clone, Copy)]
+pub enum Format {
+    Markdown,
+    Html,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Markdown => "md",
+            Format::Html => "html",
+        }
+    }
+
+    fn page(self, title: &str, parts: &[Part]) -> String {
+        match self {
+            Format::Markdown => markdown(parts),
+            Format::Html => html(title, parts),
+        }
+    }
+}
+
+pub struct Page {
+    pub file: String,
+    pub contents: String,
+}
+
+pub fn render(report: &Report, format: Format) -> Vec<Page> {
+    let mut pages = vec![Page {
+        file: format!("index.{}", format.extension()),
+        contents: format.page("Rust by Example", &index(report, format)),
+    }];
+    for chapter in &report.chapters {
+        pages.push(Page {
+            file: file(chapter.chapter, format),
+            contents: format.page(chapter.chapter.title, &chapter_parts(chapter)),
+        });
+    }
+    pages
+}
+
+pub fn write(dir: &Path, pages: &[Page]) -> io::Result<()> {
+    fs::create_dir_all(dir)?;
+    for page in pages {
+        fs::write(dir.join(&page.file), &page.contents)?;
+    }
+    Ok(())
+}
+
+fn file(chapter: &Chapter, format: Format) -> String {
+    format!("{}.{}", chapter.name(), format.extension())
+}
+
+enum Part {
+    Title(String),
+    Heading { id: &'static str, text: &'static str },
+    Prose(String),
+    Code(String),
+    Output { label: &'static str, text: String },
+    Note(String),
+    Contents { title: &'static str, file: String, sections: Vec<(&'static str, &'static str)> },
+}
+
+fn index(report: &Report, format: Format) -> Vec<Part> {
+    let mut parts = vec![Part::Title("Rust by Example".to_owned())];
+    for chapter in &report.chapters {
+        parts.push(Part::Contents {
+            title: chapter.chapter.title,
+            file: file(chapter.chapter, format),
+            sections: chapter.sections.iter()
+                .map(|section| (section.entry.section.name, section.entry.section.title))
+                .collect(),
+        });
+    }
+    parts
+}
+
+fn chapter_parts(chapter: &ChapterReport) -> Vec<Part> {
+    let mut parts = vec![Part::Title(chapter.chapter.title.to_owned())];
+    for section in &chapter.sections {
+        section_parts(section, &mut parts);
+    }
+    parts
+}
+
+fn section_parts(section: &SectionReport, parts: &mut Vec<Part>) {
+    let chapter = section.entry.chapter;
+    let name = section.entry.section.name;
+    let names: Vec<&str> = chapter.sections.iter().map(|section| section.name).collect();
+
+    parts.push(Part::Heading { id: name, text: section.entry.section.title });
+    let preamble = source::preamble(chapter.source, name, &names).unwrap_or_default();
+    let body = source::body(chapter.source, name).unwrap_or_default();
+    parts.extend(blocks(&preamble));
+    parts.extend(blocks(&body));
+
+    let outcome = &section.outcome;
+    let stdout = outcome.captured.stdout();
+    let stderr = outcome.captured.stderr();
+    let silent = stdout.is_empty() && stderr.is_empty();
+    if !stdout.is_empty() {
+        parts.push(Part::Output { label: "Output", text: stdout });
+    }
+    if !stderr.is_empty() {
+        parts.push(Part::Output { label: "Standard error", text: stderr });
+    }
+    match (&outcome.panic, section.entry.section.panics) {
+        (Some(panic), true) => parts.push(Part::Note(format!("This section panics on purpose: {}.", panic))),
+        (Some(panic), false) => parts.push(Part::Note(format!("This section panicked {}.", panic))),
+        (None, _) if silent => {
+            parts.push(Part::Note("This section prints nothing.".to_owned()));
+        }
+        (None, _) => {}
+    }
+    if let Verdict::Mismatch { .. } = section.verdict {
+        parts.push(Part::Note("The output does not match the output annotated in the code.".to_owned()));
+    }
+}
+
+/*
+Splits source lines into prose, from the `/* ... */` block comments, and the
+code between them, which keeps its `//` comments and loses its common
+indentation.
+ */
+fn blocks(lines: &[&str]) -> Vec<Part> {
+    let mut parts = Vec::new();
+    let mut prose: Vec<&str> = Vec::new();
+    let mut code: Vec<&str> = Vec::new();
+
+    for (line, kind) in lines.iter().zip(source::classify(lines)) {
+        if let Line::BlockComment(text) = kind {
+            push_code(&mut parts, &mut code);
+            let text = text.strip_prefix("/*").unwrap_or(text);
+            prose.push(text.strip_suffix("*/").unwrap_or(text).trim());
+        } else {
+            push_prose(&mut parts, &mut prose);
+            code.push(line);
+        }
+    }
+    push_code(&mut parts, &mut code);
+    push_prose(&mut parts, &mut prose);
+    parts
+}
+
+fn push_prose(parts: &mut Vec<Part>, prose: &mut Vec<&str>) {
+    let text = prose.join("\n").trim().to_owned();
+    if !text.is_empty() {
+        parts.push(Part::Prose(text));
+    }
+    prose.clear();
+}
+
+fn push_code(parts: &mut Vec<Part>, code: &mut Vec<&str>) {
+    let indent = code.iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min();
+    if let Some(indent) = indent {
+        let lines: Vec<&str> = code.iter()
+            .map(|line| line.get(indent..).unwrap_or("").trim_end())
+            .collect();
+        parts.push(Part::Code(lines.join("\n").trim_matches('\n').to_owned()));
+    }
+    code.clear();
+}
+
+fn markdown(parts: &[Part]) -> String {
+    let mut page = String::new();
+    for part in parts {
+        match part {
+            Part::Title(text) => page.push_str(&format!("# {}\n\n", text)),
+            Part::Heading { text, .. } => page.push_str(&format!("## {}\n\n", text)),
+            Part::Prose(text) => page.push_str(&format!("{}\n\n", text)),
+            Part::Code(code) => page.push_str(&format!("```rust\n{}\n```\n\n", code)),
+            Part::Output { label, text } => {
+                page.push_str(&format!("{}:\n\n```text\n{}\n```\n\n", label, text.trim_end_matches('\n')));
+            }
+            Part::Note(text) => page.push_str(&format!("> {}\n\n", text)),
+            Part::Contents { title, file, sections } => {
+                page.push_str(&format!("## [{}]({})\n\n", title, file));
+                for (_, title) in sections {
+                    page.push_str(&format!("- {}\n", title));
+                }
+                page.push('\n');
+            }
+        }
+    }
+    page.truncate(page.trim_end().len());
+    page.push('\n');
+    page
+}
+
+const STYLE: &str = "\
+body { max-width: 50em; margin: 2em auto; padding: 0 1em; font-family: sans-serif; line-height: 1.5; }
+pre { background: #f6f8fa; padding: 0.75em; overflow-x: auto; }
+pre.output { background: #fdfdf3; }
+.prose { white-space: pre-line; }
+.note { border-left: 4px solid #d0a000; padding-left: 0.75em; }";
+
+fn html(title: &str, parts: &[Part]) -> String {
+    let mut page = String::new();
+    page.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    page.push_str(&format!("<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n", escape_xml(title), STYLE));
+    for part in parts {
+        match part {
+            Part::Title(text) => page.push_str(&format!("<h1>{}</h1>\n", escape_xml(text))),
+            Part::Heading { id, text } => page.push_str(&format!("<h2 id=\"{}\">{}</h2>\n", id, escape_xml(text))),
+            Part::Prose(text) => page.push_str(&format!("<p class=\"prose\">{}</p>\n", escape_xml(text))),
+            Part::Code(code) => page.push_str(&format!("<pre><code class=\"language-rust\">{}</code></pre>\n", escape_xml(code))),
+            Part::Output { label, text } => {
+                page.push_str(&format!("<p>{}:</p>\n<pre class=\"output\">{}</pre>\n", label, escape_xml(text)));
+            }
+            Part::Note(text) => page.push_str(&format!("<p class=\"note\">{}</p>\n", escape_xml(text))),
+            Part::Contents { title, file, sections } => {
+                page.push_str(&format!("<h2><a href=\"{}\">{}</a></h2>\n<ul>\n", file, escape_xml(title)));
+                for (name, title) in sections {
+                    page.push_str(&format!("<li><a href=\"{}#{}\">{}</a></li>\n", file, name, escape_xml(title)));
+                }
+                page.push_str("</ul>\n");
+            }
+        }
+    }
+    page.push_str("</body>\n</html>\n");
+    page
+}
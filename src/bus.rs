@@ -0,0 +1,311 @@
+use std::cell::Cell;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::rc::Rc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::c_custom_types::WebEvent;
+pub use crate::web_event::Kind;
+use crate::web_event::{self, Error};
+
+/*
+Dispatches `WebEvent`s to the handlers subscribed to their kind, records
+them with the time they happened, and replays recordings through a bus, for
+replaying the sessions of UI tests. A recording is the text form of
+`web_event` with the seconds since the start of the recording in front:
+
+    0.000 page loaded
+    0.250 pressed 'x'.
+    1.500 clicked at x=20, y=80.
+
+Time comes from a `Clock`, the `SystemClock` for real sessions and a
+`ManualClock` that only moves when told, so that tests record and replay
+deterministically.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Subscription(usize);
+
+struct Handler<'a> {
+    subscription: Subscription,
+    kind: Option<Kind>,
+    handle: Box<dyn FnMut(&WebEvent) + 'a>,
+}
+
+/*
+Handlers run in the order they subscribed. They may borrow from their
+surroundings for as long as the bus lives.
+ */
+#[derive(Default)]
+pub struct Bus<'a> {
+    handlers: Vec<Handler<'a>>,
+    subscriptions: usize,
+}
+
+impl<'a> Bus<'a> {
+    pub fn new() -> Bus<'a> {
+        Bus::default()
+    }
+
+    pub fn subscribe<F>(&mut self, kind: Kind, handle: F) -> Subscription
+        where F: FnMut(&WebEvent) + 'a {
+        self.add(Some(kind), Box::new(handle))
+    }
+
+    pub fn subscribe_all<F>(&mut self, handle: F) -> Subscription
+        where F: FnMut(&WebEvent) + 'a {
+        self.add(None, Box::new(handle))
+    }
+
+    /*
+    Whether the subscription was still there.
+     */
+    pub fn unsubscribe(&mut self, subscription: Subscription) -> bool {
+        let before = self.handlers.len();
+        self.handlers.retain(|handler| handler.subscription != subscription);
+        self.handlers.len() != before
+    }
+
+    /*
+    Hands the event to its handlers, and tells how many there were.
+     */
+    pub fn publish(&mut self, event: &WebEvent) -> usize {
+        let kind = Kind::of(event);
+        let mut handled = 0;
+        for handler in &mut self.handlers {
+            if handler.kind.is_none_or(|subscribed| subscribed == kind) {
+                (handler.handle)(event);
+                handled += 1;
+            }
+        }
+        handled
+    }
+
+    fn add(&mut self, kind: Option<Kind>, handle: Box<dyn FnMut(&WebEvent) + 'a>) -> Subscription {
+        let subscription = Subscription(self.subscriptions);
+        self.subscriptions += 1;
+        self.handlers.push(Handler { subscription, kind, handle });
+        subscription
+    }
+}
+
+/*
+The time since some start, and a way to wait.
+ */
+pub trait Clock {
+    fn now(&self) -> Duration;
+    fn sleep(&mut self, duration: Duration);
+}
+
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SystemClock {
+    pub fn new() -> SystemClock {
+        SystemClock { start: Instant::now() }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/*
+A clock that stands still until it is advanced, by `advance` or by sleeping
+on it. Clones share the same time, so one clone can drive a recorder or a
+replayer while the test reads or moves the time with the other.
+ */
+#[derive(Clone, Default)]
+pub struct ManualClock {
+    now: Rc<Cell<Duration>>,
+}
+
+impl ManualClock {
+    pub fn new() -> ManualClock {
+        ManualClock::default()
+    }
+
+    /*
+    Stops at `Duration::MAX`, which stands for never.
+     */
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get().saturating_add(duration));
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+/*
+An event of a recording, with the time since the recording started.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Timed {
+    pub at: Duration,
+    pub event: WebEvent,
+}
+
+impl fmt::Display for Timed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", seconds(self.at), self.event)
+    }
+}
+
+/*
+With millisecond precision, like `1.250`.
+ */
+fn seconds(duration: Duration) -> String {
+    format!("{}.{:03}", duration.as_secs(), duration.subsec_millis())
+}
+
+/*
+Writes the events it is given as lines of a recording, timed by its clock
+from when it was made. Writing stops at the first error, which `finish`
+returns, so that `record` can be called from a handler of a bus:
+
+    let mut recorder = Recorder::create("session.txt", SystemClock::new())?;
+    let mut bus = Bus::new();
+    bus.subscribe_all(|event| recorder.record(event));
+    ...
+    drop(bus);
+    recorder.finish()?;
+ */
+pub struct Recorder<W: Write, C: Clock> {
+    out: W,
+    clock: C,
+    start: Duration,
+    error: Option<io::Error>,
+}
+
+impl<C: Clock> Recorder<BufWriter<File>, C> {
+    pub fn create<P: AsRef<Path>>(path: P, clock: C) -> io::Result<Recorder<BufWriter<File>, C>> {
+        Ok(Recorder::new(BufWriter::new(File::create(path)?), clock))
+    }
+}
+
+impl<W: Write, C: Clock> Recorder<W, C> {
+    pub fn new(out: W, clock: C) -> Recorder<W, C> {
+        let start = clock.now();
+        Recorder { out, clock, start, error: None }
+    }
+
+    pub fn record(&mut self, event: &WebEvent) {
+        if self.error.is_some() {
+            return;
+        }
+        let timed = Timed { at: self.clock.now().saturating_sub(self.start), event: event.clone() };
+        if let Err(err) = writeln!(self.out, "{}", timed) {
+            self.error = Some(err);
+        }
+    }
+
+    /*
+    Flushes the recording and gives back where it was written.
+     */
+    pub fn finish(mut self) -> io::Result<W> {
+        if let Some(err) = self.error.take() {
+            return Err(err);
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+/*
+Reads the lines of a recording, skipping blank ones. The times must not go
+back.
+ */
+pub fn read_recording(text: &str) -> Result<Vec<Timed>, Error> {
+    let mut recording: Vec<Timed> = Vec::new();
+    for (index, line) in text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let number = index + 1;
+        let syntax = |column: usize, message: String| Error::Syntax { line: number, column, message };
+
+        let (time, event) = line.split_once(' ').ok_or_else(|| syntax(1, "expected the seconds, a space and an event".to_owned()))?;
+        let at = parse_seconds(time).ok_or_else(|| syntax(1, format!("`{}` is not a time in seconds, like `1.250`", time)))?;
+        let event = web_event::from_text(event).map_err(|err| match err {
+            Error::Syntax { column, message, .. } => syntax(column + time.chars().count() + 1, message),
+            err => err,
+        })?;
+        if let Some(last) = recording.last().filter(|last| last.at > at) {
+            return Err(syntax(1, format!("`{}` is before `{}`, the time of the event before", time, seconds(last.at))));
+        }
+        recording.push(Timed { at, event });
+    }
+    Ok(recording)
+}
+
+pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Vec<Timed>> {
+    let text = fs::read_to_string(path)?;
+    read_recording(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+fn parse_seconds(text: &str) -> Option<Duration> {
+    let (seconds, fraction) = text.split_once('.').unwrap_or((text, ""));
+    let digits = |part: &str| part.bytes().all(|c| c.is_ascii_digit());
+    if seconds.is_empty() || !digits(seconds) || !digits(fraction) || fraction.len() > 9 {
+        return None;
+    }
+    let nanos = format!("{:0<9}", fraction).parse().ok()?;
+    Some(Duration::new(seconds.parse().ok()?, nanos))
+}
+
+/*
+Publishes the events of a recording on a bus as they were timed, waiting on
+its clock between them. A speed of `2.0` replays twice as fast, and
+`f64::INFINITY` without waiting at all. A speed so slow that an event would
+be due after `Duration::MAX` waits that long for it.
+ */
+pub struct Replayer<C: Clock> {
+    clock: C,
+    speed: f64,
+}
+
+impl<C: Clock> Replayer<C> {
+    pub fn new(clock: C) -> Replayer<C> {
+        Replayer { clock, speed: 1.0 }
+    }
+
+    pub fn speed(mut self, speed: f64) -> Result<Replayer<C>, String> {
+        if speed.is_nan() || speed <= 0.0 {
+            return Err(format!("the speed of a replay must be positive, got {}", speed));
+        }
+        self.speed = speed;
+        Ok(self)
+    }
+
+    pub fn replay(&mut self, recording: &[Timed], bus: &mut Bus) {
+        let start = self.clock.now();
+        for timed in recording {
+            let after = Duration::try_from_secs_f64(timed.at.as_secs_f64() / self.speed).unwrap_or(Duration::MAX);
+            let due = start.saturating_add(after);
+            let now = self.clock.now();
+            if due > now {
+                self.clock.sleep(due - now);
+            }
+            bus.publish(&timed.event);
+        }
+    }
+}
@@ -1,27 +1,32 @@
-pub fn main() {
-    structures();
-    enums();
-    web_events();
-    type_aliases();
-    linked_list();
-    constants();
-}
+#![allow(unused_imports)]
+#![allow(clippy::useless_format)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("structures", "Structures", structures),
+    Section::new("enums", "C-like enums", enums),
+    Section::new("web_events", "Enums", web_events),
+    Section::new("type_aliases", "Type aliases", type_aliases),
+    Section::new("linked_list", "Testcase: linked-list", linked_list),
+    Section::new("constants", "Constants", constants),
+];
 
 // Unit Struct, which useful for generics
-struct Unit;
+pub struct Unit;
 
 // Tuple Struct
-struct Pair(i32, f32);
+pub struct Pair(pub i32, pub f32);
 
 // Classic Struct
-struct Point {
-    x: f32,
-    y: f32,
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
 }
 
-struct Rectangle {
-    top_left: Point,
-    bottom_right: Point,
+pub struct Rectangle {
+    pub top_left: Point,
+    pub bottom_right: Point,
 }
 
 fn structures() {
@@ -53,13 +58,13 @@ fn structures() {
     };
 }
 
-enum Number {
+pub enum Number {
     Zero,
     One,
     Two,
 }
 
-enum Color {
+pub enum Color {
     Red = 0xff0000,
     Green = 0x00ff00,
     Blue = 0x0000ff,
@@ -77,7 +82,8 @@ fn enums() {
     // violets are #0000ff
 }
 
-enum WebEvent {
+#[derive(Clone, Debug, PartialEq)]
+pub enum WebEvent {
     PageLoad,
     PageUnload,
     KeyPress(char),
@@ -85,7 +91,7 @@ enum WebEvent {
     Click {x: i64, y: i64},
 }
 
-fn inspect(event: WebEvent) {
+pub fn inspect(event: WebEvent) {
     match event {
         WebEvent::PageLoad => println!("page loaded"),
         WebEvent::PageUnload => println!("page unloaded"),
@@ -116,13 +122,13 @@ fn web_events() {
     // page unloaded
 }
 
-enum VeryVerboseEnumOfThingsToDoWithNumbers {
+pub enum VeryVerboseEnumOfThingsToDoWithNumbers {
     Add,
     Subtract,
 }
 
 impl VeryVerboseEnumOfThingsToDoWithNumbers {
-    fn run(&self, x: i32, y: i32) -> i32 {
+    pub fn run(&self, x: i32, y: i32) -> i32 {
         match self {
             Self::Add => x + y,
             Self::Subtract => x - y,
@@ -130,7 +136,7 @@ impl VeryVerboseEnumOfThingsToDoWithNumbers {
     }
 }
 
-type Operations = VeryVerboseEnumOfThingsToDoWithNumbers;
+pub type Operations = VeryVerboseEnumOfThingsToDoWithNumbers;
 
 fn type_aliases() {
     let _x = Operations::Add;
@@ -138,37 +144,247 @@ fn type_aliases() {
 
 use LinkedList::*;
 
-enum LinkedList {
-    Node(u32, Box<LinkedList>),
+pub enum LinkedList<T> {
+    Node(T, Box<LinkedList<T>>),
     Nil,
 }
 
-use std::fmt::format;
+use std::fmt::{self, format};
+use std::mem::{self, ManuallyDrop};
+use std::ptr;
 
-impl LinkedList {
-    fn new() -> LinkedList {
+impl<T> LinkedList<T> {
+    pub fn new() -> LinkedList<T> {
         Nil
     }
 
-    fn prepend(self, elem: u32) -> LinkedList {
+    pub fn prepend(self, elem: T) -> LinkedList<T> {
         Node(elem, Box::new(self))
     }
 
-    fn len(&self) -> u32 {
-        match *self {
-            Node(_, ref tail) => 1 + tail.len(),
-            Nil => 0,
+    pub fn push_front(&mut self, elem: T) {
+        let tail = mem::replace(self, Nil);
+        *self = Node(elem, Box::new(tail));
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        let (elem, tail) = mem::replace(self, Nil).into_parts()?;
+        *self = *tail;
+        Some(elem)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        match self {
+            Node(elem, _) => Some(elem),
+            Nil => None,
+        }
+    }
+
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        match self {
+            Node(elem, _) => Some(elem),
+            Nil => None,
+        }
+    }
+
+    // Relinks the nodes, so no element is moved to a new box
+    pub fn reverse(&mut self) {
+        let mut rest = mem::replace(self, Nil);
+        while let Some((elem, mut tail)) = rest.into_parts() {
+            rest = mem::replace(&mut *tail, mem::replace(self, Nil));
+            *self = Node(elem, tail);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(*self, Nil)
+    }
+
+    // Walks the nodes instead of recursing, so long lists don't overflow the stack
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: Some(self) }
+    }
+
+    // `Drop` forbids moving the fields out of a node, so they are read out
+    // of a node that is never dropped
+    fn into_parts(self) -> Option<(T, Box<LinkedList<T>>)> {
+        let list = ManuallyDrop::new(self);
+        match &*list {
+            // SAFETY: `ManuallyDrop` keeps `list` from dropping its fields, and
+            // `list` goes out of scope right after without being used again,
+            // so each field is read exactly once and the copy returned here is
+            // its only owner. Nothing is dropped twice or leaked.
+            Node(elem, tail) => unsafe { Some((ptr::read(elem), ptr::read(tail))) },
+            Nil => None,
+        }
+    }
+}
+
+impl<T: fmt::Display> LinkedList<T> {
+    pub fn stringify(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for elem in self {
+            write!(f, "{}, ", elem)?;
+        }
+        write!(f, "Nil")
+    }
+}
+
+// The derived `Debug` would recurse into every `Box` like the default drop
+// below, so the nodes are written in a loop and closed all at once
+impl<T: fmt::Debug> fmt::Debug for LinkedList<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut nodes = 0;
+        for elem in self {
+            write!(f, "Node({:?}, ", elem)?;
+            nodes += 1;
+        }
+        write!(f, "Nil")?;
+        for _ in 0..nodes {
+            write!(f, ")")?;
+        }
+        Ok(())
+    }
+}
+
+// The default drop would recurse into every `Box`, one stack frame per node.
+// Unlinking each tail first leaves every node to drop with a `Nil` tail, and
+// dropping a `Nil` returns right away, so the drops nested in this one stop
+// after a single level.
+impl<T> Drop for LinkedList<T> {
+    fn drop(&mut self) {
+        let Node(_, tail) = self else { return };
+        let mut rest = mem::replace(&mut **tail, Nil);
+        while let Node(_, tail) = &mut rest {
+            rest = mem::replace(&mut **tail, Nil);
+        }
+    }
+}
+
+impl<T: Clone> Clone for LinkedList<T> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq> PartialEq for LinkedList<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        LinkedList::new()
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: &'a LinkedList<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match self.next {
+            Node(elem, tail) => {
+                self.next = tail;
+                Some(elem)
+            }
+            Nil => None,
         }
     }
+}
 
-    fn stringify(&self) -> String {
-        match *self {
-            Node(elem, ref tail) => {
-                format!("{}, {}", elem, tail.stringify())
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut LinkedList<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<&'a mut T> {
+        match self.next.take()? {
+            Node(elem, tail) => {
+                self.next = Some(tail);
+                Some(elem)
             }
-            Nil => {
-                format!("Nil")
-            },
+            Nil => None,
+        }
+    }
+}
+
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+// Keeps the order of the iterator, so `[1, 2, 3]` collects into `1, 2, 3, Nil`
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> LinkedList<T> {
+        let mut list = LinkedList::new();
+        list.extend(iter);
+        list
+    }
+}
+
+// Appends to the back, after walking to the `Nil` at the end
+impl<T> Extend<T> for LinkedList<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut end = self;
+        while let Node(_, tail) = end {
+            end = tail;
+        }
+        for elem in iter {
+            *end = Node(elem, Box::new(Nil));
+            let Node(_, tail) = end else { unreachable!() };
+            end = tail;
         }
     }
 }
@@ -190,10 +406,10 @@ fn linked_list() {
     // 3, 2, 1, Nil
 }
 
-static LANGUAGE: &str = "Rust";
-const THRESHOLD: i32 = 10;
+pub static LANGUAGE: &str = "Rust";
+pub const THRESHOLD: i32 = 10;
 
-fn is_big(n: i32) -> bool {
+pub fn is_big(n: i32) -> bool {
     n > THRESHOLD
 }
 
@@ -0,0 +1,63 @@
+pub struct Chapter {
+    pub module: &'static str,
+    pub title: &'static str,
+    pub sections: &'static [Section],
+    pub source: &'static str,
+}
+
+impl Chapter {
+    pub const fn new(
+        module: &'static str,
+        title: &'static str,
+        sections: &'static [Section],
+        source: &'static str,
+    ) -> Chapter {
+        Chapter { module, title, sections, source }
+    }
+
+    /*
+    Chapter modules are prefixed with a letter to keep them in book order,
+    e.g. `h_flow_of_control` is addressed as `flow_of_control`.
+     */
+    pub fn name(&self) -> &'static str {
+        match self.module.split_once('_') {
+            Some((_, name)) => name,
+            None => self.module,
+        }
+    }
+
+    pub fn section(&self, name: &str) -> Option<&'static Section> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    pub fn run(&self) {
+        for section in self.sections {
+            (section.run)();
+        }
+    }
+}
+
+/*
+A section is one private example function of a chapter, such as
+`h_flow_of_control::match_guards`, exposed through the chapter's `SECTIONS`.
+ */
+pub struct Section {
+    pub name: &'static str,
+    pub title: &'static str,
+    pub run: fn(),
+    pub panics: bool,
+}
+
+impl Section {
+    pub const fn new(name: &'static str, title: &'static str, run: fn()) -> Section {
+        Section { name, title, run, panics: false }
+    }
+
+    /*
+    Marks a section that panics on purpose, the runner leaves it out
+    unless asked to run it.
+     */
+    pub const fn panicking(self) -> Section {
+        Section { panics: true, ..self }
+    }
+}
@@ -0,0 +1,476 @@
+use std::io;
+use std::mem;
+use std::path::Path;
+
+use rust_by_example::bits::{self, IntType};
+use rust_by_example::book;
+use rust_by_example::golden::Verdict;
+use rust_by_example::layout::{self, Layout};
+use rust_by_example::log::{Level, Logger};
+use rust_by_example::output::{Output, Stream};
+use rust_by_example::registry::{self, Entry, CHAPTERS};
+use rust_by_example::report::{self, Report};
+use rust_by_example::runner;
+
+pub const USAGE: &str = "\
+usage: rust-by-example <command>
+
+commands:
+    list                 list every chapter
+    list <selector>...   list the matching sections with their titles
+    run <selector>...    run the matching sections
+    run --all            run every chapter in book order
+    verify [<selector>...]
+                         check the output of the sections against the
+                         expected output annotated in their comments
+    export <dir> [<selector>...]
+                         write the chapters as a book, with the prose, the
+                         code and the output of every section
+    repl                 browse, run and check the sections interactively
+    bits <expression>    evaluate an integer expression like `0x80u8 >> 2` and
+                         show the result in every base and as bits
+    layout [<type>...]   show the size, alignment, field offsets and padding
+                         of the chapters' structs, like `Book`, and which
+                         enums keep their empty variant in a niche
+    help                 show this message
+
+options for `run`:
+    --quiet              do not print chapter and section headings
+    --output <file>      write the output of the sections to a file
+
+options for `run`, `verify`, `export` and `repl`:
+    --log <filters>      which log records to show, a level for every target
+                         or for one, like `debug` or `hello_world=trace,warn`;
+                         levels are `error`, `warn`, `info` (the default),
+                         `debug`, `trace` and `off`
+    --verbose, -v        show debug records, like `--log debug`
+    --log-file <file>    append the log records to a file instead of stderr
+    --timestamps         prefix the log records with the time
+
+options for `verify`:
+    --format <format>    report as `text` (the default), `json` or `junit`
+
+options for `export`:
+    --format <format>    write `markdown` (the default) or `html` pages
+
+options for `bits`:
+    --type <type>        the type of the unsuffixed literals, `i32` by default
+
+options for `run`, `verify` and `export`:
+    --include-panicking  also run the sections that panic on purpose, like
+                         calling the diverging `foo()` in `functions`
+
+selectors:
+    flow_of_control                   a whole chapter
+    flow_of_control::match_guards     a single section
+    scoping_rules::lifetime_*         sections matching a glob (`*` and `?`)";
+
+pub enum Command {
+    List,
+    ListSections(Vec<Entry>),
+    Run { entries: Vec<Entry>, quiet: bool, output: Option<String>, log: Log },
+    Verify { entries: Vec<Entry>, format: Format, log: Log },
+    Export { entries: Vec<Entry>, dir: String, format: book::Format, log: Log },
+    Repl(Log),
+    Bits { expression: String, int_type: Option<IntType> },
+    Layout { layouts: Vec<Layout>, niches: bool },
+    Help,
+}
+
+/*
+The log options of the commands that run sections, for `main` to install
+the logger with.
+ */
+#[derive(Default)]
+pub struct Log {
+    pub logger: Logger,
+    pub file: Option<String>,
+}
+
+impl Log {
+    /*
+    Whether `arg` was a log option, taking its value from `args`.
+     */
+    fn parse<I>(&mut self, arg: &str, args: &mut I) -> Result<bool, String>
+        where I: Iterator<Item = String> {
+        match arg {
+            "--verbose" | "-v" => self.logger = mem::take(&mut self.logger).level(Some(Level::Debug)),
+            "--timestamps" => self.logger = mem::take(&mut self.logger).timestamps(true),
+            "--log" => match args.next() {
+                Some(filters) => self.logger = mem::take(&mut self.logger).filters(&filters)?,
+                None => return Err("`--log` needs filters, like `debug` or `hello_world=trace`".to_owned()),
+            },
+            "--log-file" => match args.next() {
+                Some(path) => self.file = Some(path),
+                None => return Err("`--log-file` needs a file".to_owned()),
+            },
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+pub fn parse<I>(args: I) -> Result<Command, String>
+    where I: IntoIterator<Item = String> {
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        None | Some("help") | Some("-h") | Some("--help") => Ok(Command::Help),
+        Some("list") => {
+            let selectors: Vec<String> = args.collect();
+            if selectors.is_empty() {
+                Ok(Command::List)
+            } else {
+                select(&selectors).map(Command::ListSections)
+            }
+        }
+        Some("run") => parse_run(args),
+        Some("verify") => parse_verify(args),
+        Some("export") => parse_export(args),
+        Some("repl") => parse_repl(args),
+        Some("bits") => parse_bits(args),
+        Some("layout") => parse_layout(args),
+        Some(command) => Err(format!("unknown command `{}`", command)),
+    }
+}
+
+fn parse_run<I>(mut args: I) -> Result<Command, String>
+    where I: Iterator<Item = String> {
+    let mut all = false;
+    let mut quiet = false;
+    let mut output = None;
+    let mut log = Log::default();
+    let mut include_panicking = false;
+    let mut selectors = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if log.parse(&arg, &mut args)? {
+            continue;
+        }
+        if arg == "--all" {
+            all = true;
+        } else if arg == "--quiet" {
+            quiet = true;
+        } else if arg == "--include-panicking" {
+            include_panicking = true;
+        } else if arg == "--output" {
+            match args.next() {
+                Some(path) => output = Some(path),
+                None => return Err("`--output` needs a file".to_owned()),
+            }
+        } else if arg.starts_with('-') {
+            return Err(format!("unknown option `{}` for `run`", arg));
+        } else {
+            selectors.push(arg);
+        }
+    }
+
+    let entries = match (all, selectors.is_empty()) {
+        (true, true) => registry::all(),
+        (true, false) => return Err("`run --all` does not take selectors".to_owned()),
+        (false, true) => return Err("`run` needs a selector or `--all`".to_owned()),
+        (false, false) => select(&selectors)?,
+    };
+    let entries = skip_panicking(entries, include_panicking)?;
+    Ok(Command::Run { entries, quiet, output, log })
+}
+
+fn parse_verify<I>(mut args: I) -> Result<Command, String>
+    where I: Iterator<Item = String> {
+    let mut include_panicking = false;
+    let mut format = Format::Text;
+    let mut log = Log::default();
+    let mut selectors = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if log.parse(&arg, &mut args)? {
+            continue;
+        }
+        if arg == "--include-panicking" {
+            include_panicking = true;
+        } else if arg == "--format" {
+            match args.next() {
+                Some(name) => format = Format::parse(&name)?,
+                None => return Err("`--format` needs `text`, `json` or `junit`".to_owned()),
+            }
+        } else if arg.starts_with('-') {
+            return Err(format!("unknown option `{}` for `verify`", arg));
+        } else {
+            selectors.push(arg);
+        }
+    }
+
+    let entries = if selectors.is_empty() {
+        registry::all()
+    } else {
+        select(&selectors)?
+    };
+    let entries = skip_panicking(entries, include_panicking)?;
+    Ok(Command::Verify { entries, format, log })
+}
+
+fn parse_export<I>(mut args: I) -> Result<Command, String>
+    where I: Iterator<Item = String> {
+    let mut include_panicking = false;
+    let mut format = book::Format::Markdown;
+    let mut log = Log::default();
+    let mut dir = None;
+    let mut selectors = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if log.parse(&arg, &mut args)? {
+            continue;
+        }
+        if arg == "--include-panicking" {
+            include_panicking = true;
+        } else if arg == "--format" {
+            format = match args.next().as_deref() {
+                Some("markdown") => book::Format::Markdown,
+                Some("html") => book::Format::Html,
+                Some(name) => return Err(format!("unknown format `{}`, expected `markdown` or `html`", name)),
+                None => return Err("`--format` needs `markdown` or `html`".to_owned()),
+            };
+        } else if arg.starts_with('-') {
+            return Err(format!("unknown option `{}` for `export`", arg));
+        } else if dir.is_none() {
+            dir = Some(arg);
+        } else {
+            selectors.push(arg);
+        }
+    }
+
+    let dir = dir.ok_or("`export` needs a directory")?;
+    let entries = if selectors.is_empty() {
+        registry::all()
+    } else {
+        select(&selectors)?
+    };
+    let entries = skip_panicking(entries, include_panicking)?;
+    Ok(Command::Export { entries, dir, format, log })
+}
+
+fn parse_repl<I>(mut args: I) -> Result<Command, String>
+    where I: Iterator<Item = String> {
+    let mut log = Log::default();
+    while let Some(arg) = args.next() {
+        if !log.parse(&arg, &mut args)? {
+            return Err(format!("`repl` takes only log options, got `{}`", arg));
+        }
+    }
+    Ok(Command::Repl(log))
+}
+
+fn parse_bits<I>(mut args: I) -> Result<Command, String>
+    where I: Iterator<Item = String> {
+    let mut int_type = None;
+    let mut words = Vec::new();
+
+    while let Some(arg) = args.next() {
+        if arg == "--type" {
+            match args.next() {
+                Some(name) => int_type = Some(name.parse().map_err(|err: bits::Error| err.to_string())?),
+                None => return Err("`--type` needs an integer type, like `u8` or `i128`".to_owned()),
+            }
+        } else {
+            words.push(arg);
+        }
+    }
+    if words.is_empty() {
+        return Err("`bits` needs an expression, like `0b0011u32 & 0b0101`".to_owned());
+    }
+    Ok(Command::Bits { expression: words.join(" "), int_type })
+}
+
+fn parse_layout<I>(args: I) -> Result<Command, String>
+    where I: Iterator<Item = String> {
+    let names: Vec<String> = args.collect();
+    if names.is_empty() {
+        return Ok(Command::Layout { layouts: layout::layouts(), niches: true });
+    }
+    let mut layouts = Vec::new();
+    for name in names {
+        match layout::find(&name) {
+            Some(layout) => layouts.push(layout),
+            None => {
+                let known: Vec<String> = layout::layouts().into_iter().map(|layout| layout.name).collect();
+                return Err(format!("no layout for `{}`, expected one of {}", name, known.join(", ")));
+            }
+        }
+    }
+    Ok(Command::Layout { layouts, niches: false })
+}
+
+fn select(selectors: &[String]) -> Result<Vec<Entry>, String> {
+    let mut entries = Vec::new();
+    for selector in selectors {
+        let selected = registry::select(selector);
+        if selected.is_empty() {
+            return Err(format!("nothing matches `{}`, see `list`", selector));
+        }
+        entries.extend(selected);
+    }
+    Ok(entries)
+}
+
+fn skip_panicking(entries: Vec<Entry>, include_panicking: bool) -> Result<Vec<Entry>, String> {
+    let entries: Vec<Entry> = entries.into_iter()
+        .filter(|entry| include_panicking || !entry.section.panics)
+        .collect();
+    if entries.is_empty() {
+        return Err("only sections that panic were selected, add `--include-panicking`".to_owned());
+    }
+    Ok(entries)
+}
+
+pub fn list() {
+    for chapter in CHAPTERS {
+        println!("{:<20} {}", chapter.name(), chapter.title);
+    }
+}
+
+pub fn list_sections(entries: &[Entry]) {
+    let width = entries.iter().map(|entry| entry.path().len()).max().unwrap_or(0);
+    for entry in entries {
+        let note = if entry.section.panics { " (panics)" } else { "" };
+        println!("{:<width$} {}{}", entry.path(), entry.section.title, note, width = width);
+    }
+}
+
+/*
+Returns whether every section ran without panicking, apart from the ones
+that panic on purpose.
+ */
+pub fn run(entries: &[Entry], quiet: bool, mut output: Output) -> io::Result<bool> {
+    let mut panicked = Vec::new();
+    let mut chapter = None;
+    for entry in entries {
+        if !quiet {
+            if chapter != Some(entry.chapter.module) {
+                chapter = Some(entry.chapter.module);
+                output.write(Stream::Stdout, &format!("=== {} ===\n", entry.chapter.title))?;
+            }
+            output.write(Stream::Stdout, &format!("--- {} ---\n", entry.section.title))?;
+        }
+
+        let outcome = runner::run_isolated(entry);
+        outcome.captured.replay(&mut output)?;
+        if let Some(panic) = outcome.panic {
+            output.write(Stream::Stderr, &format!("section `{}` panicked {}\n", entry.path(), panic))?;
+            if !entry.section.panics {
+                panicked.push(entry.path());
+            }
+        }
+    }
+
+    if !panicked.is_empty() {
+        let message = format!("{} section(s) panicked: {}\n", panicked.len(), panicked.join(", "));
+        output.write(Stream::Stderr, &message)?;
+    }
+    Ok(panicked.is_empty())
+}
+
+pub enum Format {
+    Text,
+    Json,
+    Junit,
+}
+
+impl Format {
+    fn parse(name: &str) -> Result<Format, String> {
+        match name {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "junit" => Ok(Format::Junit),
+            _ => Err(format!("unknown format `{}`, expected `text`, `json` or `junit`", name)),
+        }
+    }
+}
+
+/*
+Returns whether every section matched its annotated output.
+ */
+pub fn verify(entries: &[Entry], format: Format) -> bool {
+    let report = report::collect(entries);
+    match format {
+        Format::Text => print_report(&report),
+        Format::Json => println!("{}", report.to_json().pretty()),
+        Format::Junit => print!("{}", report.to_junit()),
+    }
+    report.summary().failed == 0
+}
+
+fn print_report(report: &Report) {
+    for section in report.sections() {
+        let path = section.entry.path();
+        let stdout = section.outcome.captured.stdout();
+
+        match &section.verdict {
+            Verdict::Pass => println!("ok       {}", path),
+            Verdict::Mismatch { missing } => {
+                println!("FAIL     {}", path);
+                println!("    expected output, in order:");
+                for (index, line) in section.expected.iter().enumerate() {
+                    let mark = if missing.contains(&index) { "missing" } else { "ok" };
+                    println!("        {:<8}{}", mark, line);
+                }
+                print_indented("actual output", &stdout);
+                let stderr = section.outcome.captured.stderr();
+                if !stderr.is_empty() {
+                    print_indented("stderr", &stderr);
+                }
+            }
+            Verdict::Panicked(panic) => {
+                println!("FAIL     {} (panicked {})", path, panic);
+                print_indented("output before the panic", &stdout);
+            }
+            Verdict::Unannotated => println!("-        {} (no annotated output)", path),
+            Verdict::Skipped(reason) => println!("skipped  {} ({})", path, reason),
+        }
+    }
+
+    let summary = report.summary();
+    println!();
+    println!(
+        "{} passed, {} failed, {} unannotated, {} skipped in {:.2?}",
+        summary.passed, summary.failed, summary.unannotated, summary.skipped, summary.duration
+    );
+}
+
+fn print_indented(heading: &str, text: &str) {
+    println!("    {}:", heading);
+    for line in text.lines() {
+        println!("        | {}", line);
+    }
+}
+
+pub fn export(entries: &[Entry], dir: &str, format: book::Format) -> io::Result<()> {
+    let report = report::collect(entries);
+    let pages = book::render(&report, format);
+    book::write(Path::new(dir), &pages)?;
+    println!("wrote {} pages to {}", pages.len(), dir);
+    Ok(())
+}
+
+pub fn bits(expression: &str, int_type: Option<IntType>) -> Result<(), bits::Error> {
+    let value = bits::eval(expression, int_type)?;
+    let radix = |radix| value.to_radix(radix).expect("a valid radix");
+    println!("{} = {}{}", expression, value, value.int_type());
+    println!("binary   {}", value.layout(4));
+    println!("octal    {}", radix(8));
+    println!("hex      {}", radix(16));
+    Ok(())
+}
+
+/*
+The niches are shown along with every layout, when no types were named.
+ */
+pub fn layout(layouts: &[Layout], niches: bool) {
+    for layout in layouts {
+        println!("{}", layout);
+    }
+    if niches {
+        for niche in layout::niches() {
+            println!("{}", niche);
+        }
+    }
+}
@@ -0,0 +1,422 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{a_hello_world, c_custom_types, h_flow_of_control};
+
+/*
+One color model for the three color types of the chapters: the `RGB (0, 3,
+254) 0x0003FE` struct of `a_hello_world`, the `Color::Red = 0xff0000`
+discriminants of `c_custom_types` and the RGB/HSV/HSL/CMY/CMYK enum matched in
+`h_flow_of_control::match_destructuring`.
+
+Every color goes through `Rgb`, 8 bits per channel. The other spaces keep
+their components as floats, hue in degrees in `0..360` and everything else in
+`0..=1`, so that converting an `Rgb` to any of them and back gives the same
+`Rgb`. Only the integer components of `h_flow_of_control::Color`, where hue is
+in whole degrees and the other components are whole percentages, lose
+precision.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsv {
+    pub hue: f64,
+    pub saturation: f64,
+    pub value: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Hsl {
+    pub hue: f64,
+    pub saturation: f64,
+    pub lightness: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cmy {
+    pub cyan: f64,
+    pub magenta: f64,
+    pub yellow: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cmyk {
+    pub cyan: f64,
+    pub magenta: f64,
+    pub yellow: f64,
+    pub key: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Syntax(String),
+    OutOfRange { what: &'static str, value: u32, max: u32 },
+    NoVariant(Rgb),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax(message) => write!(f, "invalid color: {}", message),
+            Error::OutOfRange { what, value, max } => write!(f, "{} {} is out of range, expected 0 to {}", what, value, max),
+            Error::NoVariant(rgb) => write!(f, "{} is not one of the variants of the color enum", rgb),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/*
+The named colors of CSS 2, with the aliases after the names they alias.
+ */
+const NAMED: &[(&str, u32)] = &[
+    ("black", 0x000000),
+    ("silver", 0xc0c0c0),
+    ("gray", 0x808080),
+    ("grey", 0x808080),
+    ("white", 0xffffff),
+    ("maroon", 0x800000),
+    ("red", 0xff0000),
+    ("purple", 0x800080),
+    ("fuchsia", 0xff00ff),
+    ("magenta", 0xff00ff),
+    ("green", 0x008000),
+    ("lime", 0x00ff00),
+    ("olive", 0x808000),
+    ("yellow", 0xffff00),
+    ("navy", 0x000080),
+    ("blue", 0x0000ff),
+    ("teal", 0x008080),
+    ("aqua", 0x00ffff),
+    ("cyan", 0x00ffff),
+    ("orange", 0xffa500),
+];
+
+impl Rgb {
+    pub const fn new(red: u8, green: u8, blue: u8) -> Rgb {
+        Rgb { red, green, blue }
+    }
+
+    /*
+    The lower 24 bits as `0xRRGGBB`; the upper bits are ignored.
+     */
+    pub const fn from_u32(rgb: u32) -> Rgb {
+        Rgb::new((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+    }
+
+    pub const fn to_u32(self) -> u32 {
+        (self.red as u32) << 16 | (self.green as u32) << 8 | self.blue as u32
+    }
+
+    pub fn named(name: &str) -> Option<Rgb> {
+        NAMED.iter()
+            .find(|(named, _)| named.eq_ignore_ascii_case(name))
+            .map(|&(_, rgb)| Rgb::from_u32(rgb))
+    }
+
+    pub fn name(self) -> Option<&'static str> {
+        NAMED.iter().find(|&&(_, rgb)| rgb == self.to_u32()).map(|&(name, _)| name)
+    }
+
+    /*
+    Like `#0003fe`, which is also how an `Rgb` displays.
+     */
+    pub fn to_hex(self) -> String {
+        format!("#{:06x}", self.to_u32())
+    }
+
+    /*
+    Like `rgb(0, 3, 254)`.
+     */
+    pub fn to_css(self) -> String {
+        format!("rgb({}, {}, {})", self.red, self.green, self.blue)
+    }
+
+    fn unit(self) -> (f64, f64, f64) {
+        (self.red as f64 / 255.0, self.green as f64 / 255.0, self.blue as f64 / 255.0)
+    }
+
+    fn from_unit(red: f64, green: f64, blue: f64) -> Rgb {
+        let channel = |unit: f64| (unit.clamp(0.0, 1.0) * 255.0).round() as u8;
+        Rgb::new(channel(red), channel(green), channel(blue))
+    }
+
+    /*
+    The hue in degrees and the chroma, the difference between the largest and
+    the smallest channel, shared by HSV and HSL.
+     */
+    fn hue_and_chroma(self) -> (f64, f64, f64, f64) {
+        let (r, g, b) = self.unit();
+        let max = r.max(g).max(b);
+        let min = r.min(g).min(b);
+        let chroma = max - min;
+
+        let hue = if chroma == 0.0 {
+            0.0
+        } else if max == r {
+            60.0 * ((g - b) / chroma).rem_euclid(6.0)
+        } else if max == g {
+            60.0 * ((b - r) / chroma + 2.0)
+        } else {
+            60.0 * ((r - g) / chroma + 4.0)
+        };
+        (hue, chroma, max, min)
+    }
+}
+
+/*
+The channels of a color with the given hue and chroma, before adding the
+smallest channel back.
+ */
+fn from_hue(hue: f64, chroma: f64) -> (f64, f64, f64) {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    }
+}
+
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_hex())
+    }
+}
+
+impl From<Rgb> for Hsv {
+    fn from(rgb: Rgb) -> Hsv {
+        let (hue, chroma, max, _) = rgb.hue_and_chroma();
+        let saturation = if max == 0.0 { 0.0 } else { chroma / max };
+        Hsv { hue, saturation, value: max }
+    }
+}
+
+impl From<Hsv> for Rgb {
+    fn from(hsv: Hsv) -> Rgb {
+        let chroma = hsv.value * hsv.saturation;
+        let (r, g, b) = from_hue(hsv.hue, chroma);
+        let min = hsv.value - chroma;
+        Rgb::from_unit(r + min, g + min, b + min)
+    }
+}
+
+impl From<Rgb> for Hsl {
+    fn from(rgb: Rgb) -> Hsl {
+        let (hue, chroma, max, min) = rgb.hue_and_chroma();
+        let lightness = (max + min) / 2.0;
+        let saturation = if chroma == 0.0 { 0.0 } else { chroma / (1.0 - (2.0 * lightness - 1.0).abs()) };
+        Hsl { hue, saturation, lightness }
+    }
+}
+
+impl From<Hsl> for Rgb {
+    fn from(hsl: Hsl) -> Rgb {
+        let chroma = (1.0 - (2.0 * hsl.lightness - 1.0).abs()) * hsl.saturation;
+        let (r, g, b) = from_hue(hsl.hue, chroma);
+        let min = hsl.lightness - chroma / 2.0;
+        Rgb::from_unit(r + min, g + min, b + min)
+    }
+}
+
+impl From<Rgb> for Cmy {
+    fn from(rgb: Rgb) -> Cmy {
+        let (r, g, b) = rgb.unit();
+        Cmy { cyan: 1.0 - r, magenta: 1.0 - g, yellow: 1.0 - b }
+    }
+}
+
+impl From<Cmy> for Rgb {
+    fn from(cmy: Cmy) -> Rgb {
+        Rgb::from_unit(1.0 - cmy.cyan, 1.0 - cmy.magenta, 1.0 - cmy.yellow)
+    }
+}
+
+impl From<Rgb> for Cmyk {
+    fn from(rgb: Rgb) -> Cmyk {
+        let (r, g, b) = rgb.unit();
+        let key = 1.0 - r.max(g).max(b);
+        if key == 1.0 {
+            return Cmyk { cyan: 0.0, magenta: 0.0, yellow: 0.0, key };
+        }
+        let ink = |channel: f64| (1.0 - channel - key) / (1.0 - key);
+        Cmyk { cyan: ink(r), magenta: ink(g), yellow: ink(b), key }
+    }
+}
+
+impl From<Cmyk> for Rgb {
+    fn from(cmyk: Cmyk) -> Rgb {
+        let channel = |ink: f64| (1.0 - ink) * (1.0 - cmyk.key);
+        Rgb::from_unit(channel(cmyk.cyan), channel(cmyk.magenta), channel(cmyk.yellow))
+    }
+}
+
+/*
+`#rgb`, `#rrggbb` and `0xRRGGBB` hex, CSS `rgb(0, 3, 254)`, with or without
+the commas, and the names of `NAMED`, all ignoring case.
+ */
+impl FromStr for Rgb {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Rgb, Error> {
+        let text = text.trim();
+        let lower = text.to_ascii_lowercase();
+
+        if let Some(hex) = lower.strip_prefix('#').or_else(|| lower.strip_prefix("0x")) {
+            return parse_hex(hex).ok_or_else(|| Error::Syntax(format!("`{}` is not a hex color", text)));
+        }
+        if let Some(arguments) = lower.strip_prefix("rgb(").and_then(|rest| rest.strip_suffix(')')) {
+            return parse_css(arguments);
+        }
+        Rgb::named(text).ok_or_else(|| Error::Syntax(format!("`{}` is not a hex, `rgb()` or named color", text)))
+    }
+}
+
+fn parse_hex(hex: &str) -> Option<Rgb> {
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    match hex.len() {
+        3 => {
+            let rgb = u32::from_str_radix(hex, 16).ok()?;
+            let double = |nibble: u32| (nibble & 0xf) * 0x11;
+            Some(Rgb::from_u32(double(rgb >> 8) << 16 | double(rgb >> 4) << 8 | double(rgb)))
+        }
+        6 => u32::from_str_radix(hex, 16).ok().map(Rgb::from_u32),
+        _ => None,
+    }
+}
+
+fn parse_css(arguments: &str) -> Result<Rgb, Error> {
+    let channels: Vec<&str> = arguments.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|channel| !channel.is_empty())
+        .collect();
+    let [red, green, blue] = channels.as_slice() else {
+        return Err(Error::Syntax(format!("`rgb({})` needs three channels", arguments)));
+    };
+    let channel = |what: &'static str, text: &str| -> Result<u8, Error> {
+        let value: u32 = text.parse().map_err(|_| Error::Syntax(format!("{} `{}` is not a number", what, text)))?;
+        u8::try_from(value).map_err(|_| Error::OutOfRange { what, value, max: 255 })
+    };
+    Ok(Rgb::new(channel("red", red)?, channel("green", green)?, channel("blue", blue)?))
+}
+
+impl From<&a_hello_world::Color> for Rgb {
+    fn from(color: &a_hello_world::Color) -> Rgb {
+        Rgb::new(color.red, color.green, color.blue)
+    }
+}
+
+impl From<Rgb> for a_hello_world::Color {
+    fn from(rgb: Rgb) -> a_hello_world::Color {
+        a_hello_world::Color { red: rgb.red, green: rgb.green, blue: rgb.blue }
+    }
+}
+
+impl From<&c_custom_types::Color> for Rgb {
+    fn from(color: &c_custom_types::Color) -> Rgb {
+        use c_custom_types::Color::*;
+        Rgb::from_u32(match color {
+            Red => Red as u32,
+            Green => Green as u32,
+            Blue => Blue as u32,
+        })
+    }
+}
+
+impl TryFrom<Rgb> for c_custom_types::Color {
+    type Error = Error;
+
+    fn try_from(rgb: Rgb) -> Result<c_custom_types::Color, Error> {
+        use c_custom_types::Color::*;
+        match rgb.to_u32() {
+            rgb if rgb == Red as u32 => Ok(Red),
+            rgb if rgb == Green as u32 => Ok(Green),
+            rgb if rgb == Blue as u32 => Ok(Blue),
+            _ => Err(Error::NoVariant(rgb)),
+        }
+    }
+}
+
+/*
+The components of the `h_flow_of_control` enum are 8-bit channels for `RGB`,
+whole degrees for hues and whole percentages for everything else.
+ */
+impl TryFrom<&h_flow_of_control::Color> for Rgb {
+    type Error = Error;
+
+    fn try_from(color: &h_flow_of_control::Color) -> Result<Rgb, Error> {
+        use h_flow_of_control::Color::*;
+        let channel = |what, value: u32| -> Result<u8, Error> {
+            u8::try_from(value).map_err(|_| Error::OutOfRange { what, value, max: 255 })
+        };
+        let hue = |value: u32| -> Result<f64, Error> {
+            if value < 360 { Ok(value as f64) } else { Err(Error::OutOfRange { what: "hue", value, max: 359 }) }
+        };
+        let percent = |what, value: u32| -> Result<f64, Error> {
+            if value <= 100 { Ok(value as f64 / 100.0) } else { Err(Error::OutOfRange { what, value, max: 100 }) }
+        };
+
+        Ok(match *color {
+            Red => Rgb::from_u32(0xff0000),
+            Blue => Rgb::from_u32(0x0000ff),
+            Green => Rgb::from_u32(0x00ff00),
+            RGB(r, g, b) => Rgb::new(channel("red", r)?, channel("green", g)?, channel("blue", b)?),
+            HSV(h, s, v) => Hsv { hue: hue(h)?, saturation: percent("saturation", s)?, value: percent("value", v)? }.into(),
+            HSL(h, s, l) => Hsl { hue: hue(h)?, saturation: percent("saturation", s)?, lightness: percent("lightness", l)? }.into(),
+            CMY(c, m, y) => Cmy { cyan: percent("cyan", c)?, magenta: percent("magenta", m)?, yellow: percent("yellow", y)? }.into(),
+            CMYK(c, m, y, k) => Cmyk {
+                cyan: percent("cyan", c)?,
+                magenta: percent("magenta", m)?,
+                yellow: percent("yellow", y)?,
+                key: percent("key", k)?,
+            }.into(),
+        })
+    }
+}
+
+fn degrees(hue: f64) -> u32 {
+    hue.round() as u32 % 360
+}
+
+fn percent(unit: f64) -> u32 {
+    (unit * 100.0).round() as u32
+}
+
+impl From<Rgb> for h_flow_of_control::Color {
+    fn from(rgb: Rgb) -> h_flow_of_control::Color {
+        h_flow_of_control::Color::RGB(rgb.red as u32, rgb.green as u32, rgb.blue as u32)
+    }
+}
+
+impl From<Hsv> for h_flow_of_control::Color {
+    fn from(hsv: Hsv) -> h_flow_of_control::Color {
+        h_flow_of_control::Color::HSV(degrees(hsv.hue), percent(hsv.saturation), percent(hsv.value))
+    }
+}
+
+impl From<Hsl> for h_flow_of_control::Color {
+    fn from(hsl: Hsl) -> h_flow_of_control::Color {
+        h_flow_of_control::Color::HSL(degrees(hsl.hue), percent(hsl.saturation), percent(hsl.lightness))
+    }
+}
+
+impl From<Cmy> for h_flow_of_control::Color {
+    fn from(cmy: Cmy) -> h_flow_of_control::Color {
+        h_flow_of_control::Color::CMY(percent(cmy.cyan), percent(cmy.magenta), percent(cmy.yellow))
+    }
+}
+
+impl From<Cmyk> for h_flow_of_control::Color {
+    fn from(cmyk: Cmyk) -> h_flow_of_control::Color {
+        h_flow_of_control::Color::CMYK(percent(cmyk.cyan), percent(cmyk.magenta), percent(cmyk.yellow), percent(cmyk.key))
+    }
+}
@@ -1,11 +1,16 @@
-pub fn main() {
-    intro();
-    mutability();
-    scope();
-    shadowing();
-    declare_first();
-    freezing();
-}
+#![allow(unused_variables)]
+#![allow(clippy::needless_late_init)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("intro", "Variable Bindings", intro),
+    Section::new("mutability", "Mutability", mutability),
+    Section::new("scope", "Scope", scope),
+    Section::new("shadowing", "Shadowing", shadowing),
+    Section::new("declare_first", "Declare first", declare_first),
+    Section::new("freezing", "Freezing", freezing),
+];
 
 fn intro() {
     let an_integer = 1u32;
@@ -1,11 +1,14 @@
 #![allow(overflowing_literals)]
+#![allow(clippy::cast_nan_to_int, clippy::unnecessary_cast, clippy::vec_init_then_push)]
 
-pub fn main() {
-    casting();
-    literals();
-    inference();
-    aliasing();
-}
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("casting", "Casting", casting),
+    Section::new("literals", "Literals", literals),
+    Section::new("inference", "Inference", inference),
+    Section::new("aliasing", "Aliasing", aliasing),
+];
 
 fn casting() {
     let decimal = 65.4321_f32;
@@ -105,9 +108,9 @@ fn inference() {
     // [5]
 }
 
-type NanoSecond = u64;
-type Inch = u64;
-type U64 = u64;
+pub type NanoSecond = u64;
+pub type Inch = u64;
+pub type U64 = u64;
 
 fn aliasing() {
     let nanoseconds: NanoSecond = 5 as U64;
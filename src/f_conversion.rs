@@ -1,14 +1,18 @@
-pub fn main() {
-    from_and_into();
-    try_from_and_try_into();
-    to_and_from_strings();
-}
+#![allow(clippy::to_string_in_format_args)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("from_and_into", "From and Into", from_and_into),
+    Section::new("try_from_and_try_into", "TryFrom and TryInto", try_from_and_try_into),
+    Section::new("to_and_from_strings", "To and from Strings", to_and_from_strings),
+];
 
 use std::convert::From;
 
 #[derive(Debug)]
-struct MyNumber {
-    value: i32,
+pub struct MyNumber {
+    pub value: i32,
 }
 
 impl From<i32> for MyNumber {
@@ -32,7 +36,7 @@ use std::convert::TryFrom;
 use std::convert::TryInto;
 
 #[derive(Debug)]
-struct EvenNumber(i32);
+pub struct EvenNumber(pub i32);
 
 impl TryFrom<i32> for EvenNumber {
     type Error = ();
@@ -77,8 +81,8 @@ fn try_from_and_try_into() {
 use std::fmt;
 use std::fmt::Formatter;
 
-struct Circle {
-    radius: i32,
+pub struct Circle {
+    pub radius: i32,
 }
 
 impl fmt::Display for Circle {
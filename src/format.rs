@@ -0,0 +1,558 @@
+use std::collections::HashMap;
+use std::fmt;
+
+/*
+A runtime version of the format strings shown in `a_hello_world::format_print`.
+A template is parsed once with the grammar of `std::fmt`,
+
+    {[argument][:[[fill]align][sign]['#']['0'][width]['.' precision][type]]}
+
+where the argument is a position (`{0}`) or a name (`{subject}`), the width
+and precision are literals or arguments (`{number:0>width$}`, `{:.*}`) and the
+type is empty, `?`, `b`, `o`, `x`, `X`, `e` or `E`. It is then rendered with
+the arguments bound from an `Args` value. Errors point at the column, counted
+in characters from 1, of the offending part of the template.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Integer(i128),
+    Float(f64),
+    String(String),
+    Char(char),
+    Bool(bool),
+}
+
+macro_rules! integer_values {
+    ($($integer:ty)*) => {
+        $(
+            impl From<$integer> for Value {
+                fn from(value: $integer) -> Value {
+                    Value::Integer(value as i128)
+                }
+            }
+        )*
+    };
+}
+
+integer_values!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 usize);
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Value {
+        Value::Float(value as f64)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Value {
+        Value::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Value {
+        Value::String(value.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Value {
+        Value::String(value)
+    }
+}
+
+impl From<char> for Value {
+    fn from(value: char) -> Value {
+        Value::Char(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Value {
+        Value::Bool(value)
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Integer(integer) => write!(f, "{}", integer),
+            Value::Float(float) => write!(f, "{}", float),
+            Value::String(string) => write!(f, "{}", string),
+            Value::Char(c) => write!(f, "{}", c),
+            Value::Bool(bool) => write!(f, "{}", bool),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Args {
+    positional: Vec<Value>,
+    named: HashMap<String, Value>,
+}
+
+impl Args {
+    pub fn new() -> Args {
+        Args::default()
+    }
+
+    pub fn with<V: Into<Value>>(mut self, value: V) -> Args {
+        self.positional.push(value.into());
+        self
+    }
+
+    pub fn with_named<V: Into<Value>>(mut self, name: &str, value: V) -> Args {
+        self.named.insert(name.to_owned(), value.into());
+        self
+    }
+}
+
+impl From<HashMap<String, Value>> for Args {
+    fn from(named: HashMap<String, Value>) -> Args {
+        Args { positional: Vec::new(), named }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub fn format(template: &str, args: &Args) -> Result<String, Error> {
+    Template::parse(template)?.render(args)
+}
+
+#[derive(Debug)]
+pub struct Template {
+    pieces: Vec<Piece>,
+}
+
+#[derive(Debug)]
+enum Piece {
+    Literal(String),
+    Placeholder { column: usize, argument: Argument, spec: Spec },
+}
+
+#[derive(Clone, Debug)]
+enum Argument {
+    Index(usize),
+    Name(String),
+}
+
+#[derive(Debug)]
+enum Count {
+    Literal(usize),
+    Argument(Argument),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Display,
+    Debug,
+    Binary,
+    Octal,
+    LowerHex,
+    UpperHex,
+    LowerExp,
+    UpperExp,
+}
+
+#[derive(Debug)]
+struct Spec {
+    fill: char,
+    align: Option<Align>,
+    plus: bool,
+    alternate: bool,
+    zero: bool,
+    width: Option<Count>,
+    precision: Option<Count>,
+    kind: Kind,
+}
+
+impl Default for Spec {
+    fn default() -> Spec {
+        Spec {
+            fill: ' ',
+            align: None,
+            plus: false,
+            alternate: false,
+            zero: false,
+            width: None,
+            precision: None,
+            kind: Kind::Display,
+        }
+    }
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Template, Error> {
+        let mut parser = Parser { chars: template.chars().collect(), position: 0, next_argument: 0 };
+        let mut pieces = Vec::new();
+        let mut literal = String::new();
+
+        while let Some(c) = parser.peek() {
+            match c {
+                '{' if parser.peek_at(1) == Some('{') => {
+                    literal.push('{');
+                    parser.position += 2;
+                }
+                '}' if parser.peek_at(1) == Some('}') => {
+                    literal.push('}');
+                    parser.position += 2;
+                }
+                '{' => {
+                    if !literal.is_empty() {
+                        pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                    }
+                    pieces.push(parser.placeholder()?);
+                }
+                '}' => return Err(parser.error("unmatched `}`, use `}}` for a literal `}`")),
+                c => {
+                    literal.push(c);
+                    parser.position += 1;
+                }
+            }
+        }
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(literal));
+        }
+        Ok(Template { pieces })
+    }
+
+    pub fn render(&self, args: &Args) -> Result<String, Error> {
+        let mut rendered = String::new();
+        for piece in &self.pieces {
+            match piece {
+                Piece::Literal(text) => rendered.push_str(text),
+                Piece::Placeholder { column, argument, spec } => {
+                    let error = |message| Error { column: *column, message };
+                    let value = argument.resolve(args).map_err(error)?;
+                    let width = spec.width.as_ref().map(|count| count.resolve(args)).transpose().map_err(error)?;
+                    let precision = spec.precision.as_ref().map(|count| count.resolve(args)).transpose().map_err(error)?;
+                    rendered.push_str(&spec.apply(value, width, precision).map_err(error)?);
+                }
+            }
+        }
+        Ok(rendered)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    next_argument: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let matches = self.peek() == Some(c);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error { column: self.position + 1, message: message.to_owned() }
+    }
+
+    fn placeholder(&mut self) -> Result<Piece, Error> {
+        let column = self.position + 1;
+        self.position += 1;
+
+        let argument = self.argument()?;
+        let mut spec = Spec::default();
+        if self.eat(':') {
+            self.spec(&mut spec)?;
+        }
+        match self.peek() {
+            Some('}') => self.position += 1,
+            Some(_) => return Err(self.error("expected `}` to close the placeholder")),
+            None => return Err(self.error("unterminated placeholder, expected `}`")),
+        }
+
+        /*
+        An implicit argument is taken after the one of a `.*` precision, as
+        `std` does.
+         */
+        let argument = match argument {
+            Some(argument) => argument,
+            None => self.next(),
+        };
+        Ok(Piece::Placeholder { column, argument, spec })
+    }
+
+    fn next(&mut self) -> Argument {
+        self.next_argument += 1;
+        Argument::Index(self.next_argument - 1)
+    }
+
+    fn argument(&mut self) -> Result<Option<Argument>, Error> {
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() => self.integer().map(|index| Some(Argument::Index(index))),
+            Some(c) if c.is_alphabetic() || c == '_' => Ok(Some(Argument::Name(self.identifier()))),
+            Some(':') | Some('}') | None => Ok(None),
+            Some(_) => Err(self.error("invalid argument, expected a position or a name")),
+        }
+    }
+
+    fn integer(&mut self) -> Result<usize, Error> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        let digits: String = self.chars[start..self.position].iter().collect();
+        digits.parse().map_err(|_| Error { column: start + 1, message: format!("`{}` is too large", digits) })
+    }
+
+    fn identifier(&mut self) -> String {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect()
+    }
+
+    fn spec(&mut self, spec: &mut Spec) -> Result<(), Error> {
+        if let Some(align) = self.peek_at(1).and_then(align) {
+            spec.fill = self.peek().unwrap_or(' ');
+            spec.align = Some(align);
+            self.position += 2;
+        } else if let Some(align) = self.peek().and_then(align) {
+            spec.align = Some(align);
+            self.position += 1;
+        }
+
+        if self.eat('+') {
+            spec.plus = true;
+        } else {
+            self.eat('-');
+        }
+        spec.alternate = self.eat('#');
+        if self.peek() == Some('0') && self.peek_at(1) != Some('$') {
+            spec.zero = true;
+            self.position += 1;
+        }
+        spec.width = self.count()?;
+
+        if self.eat('.') {
+            if self.eat('*') {
+                spec.precision = Some(Count::Argument(self.next()));
+            } else {
+                match self.count()? {
+                    Some(count) => spec.precision = Some(count),
+                    None => return Err(self.error("expected a precision after `.`")),
+                }
+            }
+        }
+
+        let start = self.position;
+        while self.peek().is_some_and(|c| c != '}') {
+            self.position += 1;
+        }
+        let kind: String = self.chars[start..self.position].iter().collect();
+        spec.kind = match kind.as_str() {
+            "" => Kind::Display,
+            "?" => Kind::Debug,
+            "b" => Kind::Binary,
+            "o" => Kind::Octal,
+            "x" => Kind::LowerHex,
+            "X" => Kind::UpperHex,
+            "e" => Kind::LowerExp,
+            "E" => Kind::UpperExp,
+            _ => return Err(Error { column: start + 1, message: format!("unknown format type `{}`", kind) }),
+        };
+        Ok(())
+    }
+
+    /*
+    A width or a precision: an integer, or an argument followed by `$`. A name
+    without `$` is the format type, as in `{:x}`, and is left for `spec`.
+     */
+    fn count(&mut self) -> Result<Option<Count>, Error> {
+        let start = self.position;
+        if !self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_') {
+            return Ok(None);
+        }
+        let argument = match self.argument()? {
+            Some(argument) => argument,
+            None => return Ok(None),
+        };
+        if self.eat('$') {
+            return Ok(Some(Count::Argument(argument)));
+        }
+        match argument {
+            Argument::Index(count) => Ok(Some(Count::Literal(count))),
+            Argument::Name(_) => {
+                self.position = start;
+                Ok(None)
+            }
+        }
+    }
+}
+
+fn align(c: char) -> Option<Align> {
+    match c {
+        '<' => Some(Align::Left),
+        '^' => Some(Align::Center),
+        '>' => Some(Align::Right),
+        _ => None,
+    }
+}
+
+impl Argument {
+    fn resolve<'a>(&self, args: &'a Args) -> Result<&'a Value, String> {
+        match self {
+            Argument::Index(index) => args.positional.get(*index).ok_or_else(|| {
+                format!("no argument at position {}, there are {}", index, args.positional.len())
+            }),
+            Argument::Name(name) => args.named.get(name).ok_or_else(|| format!("no argument named `{}`", name)),
+        }
+    }
+}
+
+impl Count {
+    fn resolve(&self, args: &Args) -> Result<usize, String> {
+        match self {
+            Count::Literal(count) => Ok(*count),
+            Count::Argument(argument) => match argument.resolve(args)? {
+                Value::Integer(count) if *count >= 0 => usize::try_from(*count).map_err(|err| err.to_string()),
+                value => Err(format!("a width or precision must be a non-negative integer, got {:?}", value)),
+            },
+        }
+    }
+}
+
+macro_rules! with_precision {
+    ($with:literal, $without:literal, $precision:expr, $value:expr) => {
+        match $precision {
+            Some(precision) => format!($with, precision, $value),
+            None => format!($without, $value),
+        }
+    };
+}
+
+impl Spec {
+    fn apply(&self, value: &Value, width: Option<usize>, precision: Option<usize>) -> Result<String, String> {
+        let numeric = matches!(value, Value::Integer(_) | Value::Float(_));
+        let body = self.body(value, precision)?;
+
+        let (sign, body) = match body.strip_prefix('-') {
+            Some(rest) if numeric => ("-", rest.to_owned()),
+            _ if numeric && self.plus => ("+", body),
+            _ => ("", body),
+        };
+        let prefix = match (self.alternate, self.kind) {
+            (true, Kind::Binary) => "0b",
+            (true, Kind::Octal) => "0o",
+            (true, Kind::LowerHex) | (true, Kind::UpperHex) => "0x",
+            _ => "",
+        };
+
+        let width = width.unwrap_or(0);
+        let length = sign.len() + prefix.len() + body.chars().count();
+        if self.zero && numeric {
+            let zeros = "0".repeat(width.saturating_sub(length));
+            return Ok(format!("{}{}{}{}", sign, prefix, zeros, body));
+        }
+
+        let text = format!("{}{}{}", sign, prefix, body);
+        let padding = width.saturating_sub(length);
+        let align = self.align.unwrap_or(if numeric { Align::Right } else { Align::Left });
+        let (before, after) = match align {
+            Align::Left => (0, padding),
+            Align::Center => (padding / 2, padding - padding / 2),
+            Align::Right => (padding, 0),
+        };
+        let fill = |count| self.fill.to_string().repeat(count);
+        Ok(format!("{}{}{}", fill(before), text, fill(after)))
+    }
+
+    fn body(&self, value: &Value, precision: Option<usize>) -> Result<String, String> {
+        let radix = |integer: i128, name: &str| {
+            if integer < 0 {
+                return Err(format!("cannot format the negative number {} as {}", integer, name));
+            }
+            Ok(match self.kind {
+                Kind::Binary => format!("{:b}", integer),
+                Kind::Octal => format!("{:o}", integer),
+                Kind::LowerHex => format!("{:x}", integer),
+                _ => format!("{:X}", integer),
+            })
+        };
+
+        match (value, self.kind) {
+            (Value::Integer(integer), Kind::Display | Kind::Debug) => Ok(integer.to_string()),
+            (Value::Integer(integer), Kind::Binary) => radix(*integer, "binary"),
+            (Value::Integer(integer), Kind::Octal) => radix(*integer, "octal"),
+            (Value::Integer(integer), Kind::LowerHex | Kind::UpperHex) => radix(*integer, "hexadecimal"),
+            (Value::Integer(integer), Kind::LowerExp) => Ok(with_precision!("{:.*e}", "{:e}", precision, integer)),
+            (Value::Integer(integer), Kind::UpperExp) => Ok(with_precision!("{:.*E}", "{:E}", precision, integer)),
+            (Value::Float(float), Kind::Display) => Ok(with_precision!("{:.*}", "{}", precision, float)),
+            (Value::Float(float), Kind::Debug) => Ok(with_precision!("{:.*?}", "{:?}", precision, float)),
+            (Value::Float(float), Kind::LowerExp) => Ok(with_precision!("{:.*e}", "{:e}", precision, float)),
+            (Value::Float(float), Kind::UpperExp) => Ok(with_precision!("{:.*E}", "{:E}", precision, float)),
+            (Value::String(string), Kind::Display) => Ok(truncate(string, precision)),
+            (Value::String(string), Kind::Debug) => Ok(format!("{:?}", string)),
+            (Value::Char(c), Kind::Display) => Ok(truncate(&c.to_string(), precision)),
+            (Value::Char(c), Kind::Debug) => Ok(format!("{:?}", c)),
+            (Value::Bool(bool), Kind::Display | Kind::Debug) => Ok(truncate(&bool.to_string(), precision)),
+            (value, kind) => Err(format!("{} cannot be formatted with `{}`", describe(value), kind.name())),
+        }
+    }
+}
+
+fn truncate(text: &str, precision: Option<usize>) -> String {
+    match precision {
+        Some(precision) => text.chars().take(precision).collect(),
+        None => text.to_owned(),
+    }
+}
+
+fn describe(value: &Value) -> &'static str {
+    match value {
+        Value::Integer(_) => "an integer",
+        Value::Float(_) => "a float",
+        Value::String(_) => "a string",
+        Value::Char(_) => "a char",
+        Value::Bool(_) => "a bool",
+    }
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Display => "{}",
+            Kind::Debug => "{:?}",
+            Kind::Binary => "{:b}",
+            Kind::Octal => "{:o}",
+            Kind::LowerHex => "{:x}",
+            Kind::UpperHex => "{:X}",
+            Kind::LowerExp => "{:e}",
+            Kind::UpperExp => "{:E}",
+        }
+    }
+}
@@ -1,6 +1,11 @@
-pub fn main() {
-    expressions();
-}
+#![allow(unused_must_use)]
+#![allow(clippy::let_unit_value, clippy::no_effect)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("expressions", "Expressions", expressions),
+];
 
 fn expressions() {
     let x = 5u32;
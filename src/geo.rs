@@ -0,0 +1,264 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::a_hello_world::City;
+
+/*
+A point on the Earth in degrees, with latitude in `-90..=90` (north is
+positive) and longitude in `-180..=180` (east is positive). It displays like
+`City` does, `53.348°N 6.260°W`, and parses that back along with degrees,
+minutes and seconds, `53°20'52"N 6°15'35"W`, and signed decimal degrees,
+`53.347778, -6.259722`.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Coordinate {
+    lat: f64,
+    lon: f64,
+}
+
+/*
+The mean radius of the Earth, in kilometres.
+ */
+pub const EARTH_RADIUS: f64 = 6371.0088;
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Syntax(String),
+    OutOfRange { what: &'static str, value: f64, range: &'static str },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax(message) => write!(f, "invalid coordinate: {}", message),
+            Error::OutOfRange { what, value, range } => {
+                write!(f, "{} {} is out of range, expected {}", what, value, range)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl Coordinate {
+    pub fn new(lat: f64, lon: f64) -> Result<Coordinate, Error> {
+        if !(-90.0..=90.0).contains(&lat) {
+            return Err(Error::OutOfRange { what: "latitude", value: lat, range: "-90 to 90 degrees" });
+        }
+        if !(-180.0..=180.0).contains(&lon) {
+            return Err(Error::OutOfRange { what: "longitude", value: lon, range: "-180 to 180 degrees" });
+        }
+        Ok(Coordinate { lat, lon })
+    }
+
+    pub fn lat(&self) -> f64 {
+        self.lat
+    }
+
+    pub fn lon(&self) -> f64 {
+        self.lon
+    }
+
+    /*
+    The great-circle distance in kilometres, by the haversine formula.
+     */
+    pub fn distance(&self, other: &Coordinate) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let half_dlat = (lat2 - lat1) / 2.0;
+        let half_dlon = (other.lon - self.lon).to_radians() / 2.0;
+
+        let a = half_dlat.sin().powi(2) + lat1.cos() * lat2.cos() * half_dlon.sin().powi(2);
+        2.0 * EARTH_RADIUS * a.sqrt().min(1.0).asin()
+    }
+
+    /*
+    The initial bearing of the great circle from `self` to `other`, in
+    degrees clockwise from north in `0..360`.
+     */
+    pub fn bearing(&self, other: &Coordinate) -> f64 {
+        let (lat1, lat2) = (self.lat.to_radians(), other.lat.to_radians());
+        let dlon = (other.lon - self.lon).to_radians();
+
+        let y = dlon.sin() * lat2.cos();
+        let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * dlon.cos();
+        (y.atan2(x).to_degrees() + 360.0) % 360.0
+    }
+
+    /*
+    Degrees, minutes and whole seconds, like `53°20'52"N 6°15'35"W`.
+     */
+    pub fn to_dms(&self) -> String {
+        format!("{} {}", dms(self.lat, 'N', 'S'), dms(self.lon, 'E', 'W'))
+    }
+}
+
+fn dms(degrees: f64, positive: char, negative: char) -> String {
+    let hemisphere = if degrees >= 0.0 { positive } else { negative };
+    let seconds = (degrees.abs() * 3600.0).round() as u64;
+    format!("{}°{}'{}\"{}", seconds / 3600, seconds / 60 % 60, seconds % 60, hemisphere)
+}
+
+impl fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lat_c = if self.lat >= 0.0 { 'N' } else { 'S' };
+        let lon_c = if self.lon >= 0.0 { 'E' } else { 'W' };
+        write!(f, "{:.3}°{} {:.3}°{}", self.lat.abs(), lat_c, self.lon.abs(), lon_c)
+    }
+}
+
+impl TryFrom<&City> for Coordinate {
+    type Error = Error;
+
+    fn try_from(city: &City) -> Result<Coordinate, Error> {
+        Coordinate::new(city.lat as f64, city.lon as f64)
+    }
+}
+
+impl FromStr for Coordinate {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Coordinate, Error> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(Error::Syntax("empty input".to_owned()));
+        }
+        if text.char_indices().any(|(index, _)| hemisphere(&text[index..]).is_some()) {
+            parse_hemispheres(text)
+        } else {
+            parse_signed(text)
+        }
+    }
+}
+
+/*
+The hemisphere `rest` starts with, which must end the angle before it, so
+that the `E` of an exponent like `1E1` is not taken for east.
+ */
+fn hemisphere(rest: &str) -> Option<char> {
+    let mut chars = rest.chars();
+    let c = chars.next().filter(|c| matches!(c, 'N' | 'S' | 'E' | 'W'))?;
+    match chars.next() {
+        None | Some(',') => Some(c),
+        Some(next) if next.is_whitespace() => Some(c),
+        Some(_) => None,
+    }
+}
+
+/*
+Two angles, each followed by its hemisphere: `N` or `S` for the latitude and
+`E` or `W` for the longitude, in either order.
+ */
+fn parse_hemispheres(text: &str) -> Result<Coordinate, Error> {
+    let mut lat = None;
+    let mut lon = None;
+    let mut angle = String::new();
+
+    for (index, c) in text.char_indices() {
+        match hemisphere(&text[index..]) {
+            Some(c) => {
+                let degrees = parse_angle(angle.trim())?;
+                let (slot, name, sign) = match c {
+                    'N' => (&mut lat, "latitude", 1.0),
+                    'S' => (&mut lat, "latitude", -1.0),
+                    'E' => (&mut lon, "longitude", 1.0),
+                    _ => (&mut lon, "longitude", -1.0),
+                };
+                if slot.replace(sign * degrees).is_some() {
+                    return Err(Error::Syntax(format!("more than one {}", name)));
+                }
+                angle.clear();
+            }
+            None if c == ',' && angle.trim().is_empty() => {}
+            None => angle.push(c),
+        }
+    }
+
+    if !angle.trim().is_empty() {
+        return Err(Error::Syntax(format!("`{}` has no hemisphere", angle.trim())));
+    }
+    match (lat, lon) {
+        (Some(lat), Some(lon)) => Coordinate::new(lat, lon),
+        (None, _) => Err(Error::Syntax("missing the latitude, marked `N` or `S`".to_owned())),
+        (_, None) => Err(Error::Syntax("missing the longitude, marked `E` or `W`".to_owned())),
+    }
+}
+
+/*
+Decimal degrees like `53.348°`, or degrees, minutes and seconds like
+`53°20'52"`, where the minutes and seconds may be left out and may use the
+prime marks `′` and `″`.
+ */
+fn parse_angle(text: &str) -> Result<f64, Error> {
+    if text.is_empty() {
+        return Err(Error::Syntax("missing an angle before the hemisphere".to_owned()));
+    }
+    let mut degrees = 0.0;
+    let mut has_units = false;
+    let mut number = String::new();
+    let mut units = ['°', '\'', '"'].iter();
+
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        let unit = match c {
+            '°' => '°',
+            '\'' | '′' => '\'',
+            '"' | '″' => '"',
+            c => {
+                number.push(c);
+                continue;
+            }
+        };
+        if !units.any(|&expected| expected == unit) {
+            return Err(Error::Syntax(format!("unexpected `{}` in `{}`", c, text)));
+        }
+        let value = parse_number(&number)?;
+        degrees += match unit {
+            '°' => value,
+            '\'' => minutes_or_seconds("minutes", value)? / 60.0,
+            _ => minutes_or_seconds("seconds", value)? / 3600.0,
+        };
+        has_units = true;
+        number.clear();
+    }
+
+    if !number.is_empty() {
+        if has_units {
+            return Err(Error::Syntax(format!("`{}` has no unit in `{}`", number, text)));
+        }
+        degrees = parse_number(&number)?;
+    }
+    if degrees < 0.0 {
+        return Err(Error::Syntax(format!("`{}` is negative, the hemisphere gives the sign", text)));
+    }
+    Ok(degrees)
+}
+
+fn minutes_or_seconds(what: &'static str, value: f64) -> Result<f64, Error> {
+    if (0.0..60.0).contains(&value) {
+        Ok(value)
+    } else {
+        Err(Error::OutOfRange { what, value, range: "0 up to 60" })
+    }
+}
+
+/*
+Signed decimal degrees, latitude first, separated by a comma or spaces.
+ */
+fn parse_signed(text: &str) -> Result<Coordinate, Error> {
+    let parts: Vec<&str> = text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+    match parts.as_slice() {
+        [lat, lon] => {
+            let degrees = |part: &str| parse_number(part.strip_suffix('°').unwrap_or(part));
+            Coordinate::new(degrees(lat)?, degrees(lon)?)
+        }
+        _ => Err(Error::Syntax(format!("expected a latitude and a longitude, got `{}`", text))),
+    }
+}
+
+fn parse_number(text: &str) -> Result<f64, Error> {
+    match text.parse::<f64>() {
+        Ok(number) if number.is_finite() => Ok(number),
+        _ => Err(Error::Syntax(format!("`{}` is not a number", text))),
+    }
+}
@@ -0,0 +1,171 @@
+use crate::registry::Entry;
+use crate::runner::Outcome;
+use crate::source::{self, Line};
+
+/*
+The expected output of a section is annotated in the source as runs of `//`
+comment lines placed directly below code that prints, e.g.
+
+    println!("{} Month is {}", 1, 31);
+    // 1 Month is 31 days
+
+or after the `);` that closes a `println!` spanning several lines. Code
+prints when it uses `print!` or `println!`, or calls a function or closure
+the chapter defines. Comment runs below other code, like the notes on type
+inference in `e_rust::inference`, are prose, as are runs separated from the
+code by a blank line, commented-out code (`// x = 1; // error!`) and lines
+without any letters or digits, such as the lifetime diagrams in
+`o_scoping_rules::lifetime`. What `unsafe` blocks print is undefined, so
+their annotations are left out too.
+ */
+pub fn expected_output(chapter_source: &str, section: &str) -> Vec<String> {
+    let body = match source::body(chapter_source, section) {
+        Some(body) => body,
+        None => return Vec::new(),
+    };
+
+    let mut expected = Vec::new();
+    let mut below_printing = false;
+    let mut in_print = false;
+    let mut blocks = Vec::new();
+    let mut unsafe_depth = None;
+    for line in source::classify(&body) {
+        match line {
+            Line::Code(line) => {
+                let (code, comment) = source::split_comment(line);
+                if unsafe_depth.is_none() && code.contains("unsafe {") {
+                    unsafe_depth = Some(blocks.len() + 1);
+                }
+
+                /*
+                A line that closes a block prints when anything in the block
+                did, like the `}` of a loop around a `println!`.
+                 */
+                let mut prints = is_print(code) || calls(code).any(|name| defines(chapter_source, name));
+                for c in code.chars() {
+                    match c {
+                        '{' => blocks.push(false),
+                        '}' => prints |= blocks.pop().unwrap_or(false),
+                        _ => {}
+                    }
+                }
+                if let Some(block) = blocks.last_mut() {
+                    *block |= prints;
+                }
+                if unsafe_depth.is_some_and(|unsafe_depth| blocks.len() < unsafe_depth) {
+                    unsafe_depth = None;
+                }
+
+                below_printing = (below_printing || prints) && unsafe_depth.is_none();
+                let closes_print = in_print && code.trim() == ");";
+                let ends_statement = code.trim_end().ends_with(';');
+                in_print = (in_print || is_print(code)) && !ends_statement;
+                if let Some(text) = comment.filter(|&text| closes_print && below_printing && is_annotation(text)) {
+                    expected.push(text.trim_end().to_owned());
+                }
+            }
+            Line::Comment(text) if below_printing => {
+                if is_annotation(text) {
+                    expected.push(text.trim_end().to_owned());
+                }
+            }
+            Line::Comment(_) => {}
+            Line::Blank | Line::BlockComment(_) => below_printing = false,
+        }
+    }
+    expected
+}
+
+fn is_annotation(text: &str) -> bool {
+    !text.contains("//") && text.chars().any(char::is_alphanumeric)
+}
+
+fn is_print(code: &str) -> bool {
+    code.contains("print!(") || code.contains("println!(")
+}
+
+/*
+The names of the functions and methods called on a line, leaving out the one
+a `fn` line defines.
+ */
+fn calls(code: &str) -> impl Iterator<Item = &str> {
+    let definition = code.trim_start().split_once("fn ").map(|(_, rest)| rest);
+    code.match_indices('(').filter_map(move |(index, _)| {
+        let before = &code[..index];
+        let start = before.rfind(|c: char| !(c.is_alphanumeric() || c == '_')).map_or(0, |start| start + 1);
+        let name = &before[start..];
+        let defined_here = definition.is_some_and(|rest| rest.starts_with(name));
+        (!name.is_empty() && !defined_here).then_some(name)
+    })
+}
+
+fn defines(chapter_source: &str, name: &str) -> bool {
+    let definitions = [
+        format!("fn {}(", name),
+        format!("fn {}<", name),
+        format!("let {} =", name),
+        format!("let mut {} =", name),
+        format!(" as {};", name),
+    ];
+    definitions.iter()
+        .any(|definition| chapter_source.contains(definition.as_str()))
+}
+
+/*
+Output that depends on the target platform, like the `cfg` examples, cannot
+match a single annotation.
+ */
+pub fn is_platform_dependent(chapter_source: &str, section: &str) -> bool {
+    source::body(chapter_source, section).is_some_and(|body| {
+        body.iter().any(|line| line.contains("cfg!(") || line.contains("#[cfg("))
+    })
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Mismatch { missing: Vec<usize> },
+    Panicked(String),
+    Unannotated,
+    Skipped(&'static str),
+}
+
+/*
+Every expected line has to appear in the actual output, in order. Sections
+often print more than they annotate, so extra output lines are fine.
+ */
+pub fn compare(expected: &[String], actual: &str) -> Verdict {
+    if expected.is_empty() {
+        return Verdict::Unannotated;
+    }
+
+    let actual: Vec<&str> = actual.lines().map(str::trim_end).collect();
+    let mut position = 0;
+    let mut missing = Vec::new();
+    for (index, line) in expected.iter().enumerate() {
+        match actual[position..].iter().position(|actual| actual == line) {
+            Some(offset) => position += offset + 1,
+            None => missing.push(index),
+        }
+    }
+
+    if missing.is_empty() {
+        Verdict::Pass
+    } else {
+        Verdict::Mismatch { missing }
+    }
+}
+
+pub fn verify(entry: &Entry, outcome: &Outcome) -> Verdict {
+    if let Some(panic) = &outcome.panic {
+        if !entry.section.panics {
+            return Verdict::Panicked(panic.to_string());
+        }
+    }
+
+    let source = entry.chapter.source;
+    if is_platform_dependent(source, entry.section.name) {
+        return Verdict::Skipped("output depends on the target platform");
+    }
+    compare(&expected_output(source, entry.section.name), &outcome.captured.stdout())
+}
@@ -1,17 +1,22 @@
-pub fn main() {
-    if_else();
-    loop_and_break();
-    nesting_and_labels();
-    returning_from_loops();
-    while_loops();
-    for_loops();
-    match_keyword();
-    match_destructuring();
-    match_guards();
-    match_binding();
-    if_let();
-    while_let();
-}
+#![allow(dead_code, unreachable_code, unused_labels, unused_variables)]
+#![allow(clippy::disallowed_names, clippy::match_single_binding, clippy::never_loop, clippy::redundant_guards, clippy::upper_case_acronyms)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("if_else", "if/else", if_else),
+    Section::new("loop_and_break", "loop", loop_and_break),
+    Section::new("nesting_and_labels", "Nesting and labels", nesting_and_labels),
+    Section::new("returning_from_loops", "Returning from loops", returning_from_loops),
+    Section::new("while_loops", "while", while_loops),
+    Section::new("for_loops", "for and range", for_loops),
+    Section::new("match_keyword", "match", match_keyword),
+    Section::new("match_destructuring", "Destructuring", match_destructuring),
+    Section::new("match_guards", "Guards", match_guards),
+    Section::new("match_binding", "Binding", match_binding),
+    Section::new("if_let", "if let", if_let),
+    Section::new("while_let", "while let", while_let),
+];
 
 fn if_else() {
     let n = 5;
@@ -196,6 +201,17 @@ fn match_keyword() {
     // true -> 1
 }
 
+pub enum Color {
+    Red,
+    Blue,
+    Green,
+    RGB(u32, u32, u32),
+    HSV(u32, u32, u32),
+    HSL(u32, u32, u32),
+    CMY(u32, u32, u32),
+    CMYK(u32, u32, u32, u32),
+}
+
 fn match_destructuring() {
     /*
     tuples
@@ -229,17 +245,6 @@ fn match_destructuring() {
     /*
     enums
      */
-    enum Color {
-        Red,
-        Blue,
-        Green,
-        RGB(u32, u32, u32),
-        HSV(u32, u32, u32),
-        HSL(u32, u32, u32),
-        CMY(u32, u32, u32),
-        CMYK(u32, u32, u32, u32),
-    }
-
     let color = Color::RGB(122, 17, 40);
     match color {
         Color::Red   =>
@@ -289,7 +294,7 @@ fn match_destructuring() {
             println!("We added 10. `mut_value`: {:?}", m);
         },
     }
-    // We added 10. `mut_value`: 13\
+    // We added 10. `mut_value`: 13
 
     /*
     structs
@@ -1,17 +1,22 @@
-pub fn main() {
-    intro();
-    associated_functions_and_methods();
-    closures();
-    closures_capturing();
-    closures_move();
-    closures_as_input_params();
-    closures_type_anonymity();
-    closures_input_functions();
-    closures_as_output_params();
-    closures_iterator_any();
-    higher_order_functions();
-    diverging_functions();
-}
+#![allow(clippy::manual_contains, clippy::manual_is_multiple_of, clippy::unnecessary_fold, clippy::unused_unit, clippy::useless_vec)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("intro", "Functions", intro),
+    Section::new("associated_functions_and_methods", "Associated functions & Methods", associated_functions_and_methods),
+    Section::new("closures", "Closures", closures),
+    Section::new("closures_capturing", "Capturing", closures_capturing),
+    Section::new("closures_move", "Capturing by move", closures_move),
+    Section::new("closures_as_input_params", "As input parameters", closures_as_input_params),
+    Section::new("closures_type_anonymity", "Type anonymity", closures_type_anonymity),
+    Section::new("closures_input_functions", "Input functions", closures_input_functions),
+    Section::new("closures_as_output_params", "As output parameters", closures_as_output_params),
+    Section::new("closures_iterator_any", "Iterator::any", closures_iterator_any),
+    Section::new("higher_order_functions", "Higher Order Functions", higher_order_functions),
+    Section::new("diverging_functions", "Diverging functions", diverging_functions),
+    Section::new("calling_diverging_functions", "Calling a diverging function", calling_diverging_functions).panicking(),
+];
 
 fn intro() {
     fn is_divisible_by(lhs: u32, rhs: u32) -> bool {
@@ -387,15 +392,14 @@ fn higher_order_functions() {
     // functional style: 5456
 }
 
-fn diverging_functions() {
-    /*
-    Diverging functions never return. They are marked using `!`, which is an empty type.
-     */
-
-    fn foo() -> ! {
-        panic!("This call never returns.");
-    }
+/*
+Diverging functions never return. They are marked using `!`, which is an empty type.
+ */
+fn foo() -> ! {
+    panic!("This call never returns.");
+}
 
+fn diverging_functions() {
     /*
     Usage of diverging concept, `match` branches.
      */
@@ -419,3 +423,8 @@ fn diverging_functions() {
     println!("Sum of odd numbers up to 9 (excluding): {}", sum_odd_numbers(9));
     // Sum of odd numbers up to 9 (excluding): 16
 }
+
+fn calling_diverging_functions() {
+    println!("Calling `foo()`");
+    foo();
+}
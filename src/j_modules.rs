@@ -1,9 +1,14 @@
-pub fn main() {
-    visibility();
-    struct_visibility();
-    use_declaration();
-    super_and_self();
-}
+#![allow(dead_code)]
+#![allow(clippy::needless_pub_self)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("visibility", "Visibility", visibility),
+    Section::new("struct_visibility", "Struct visibility", struct_visibility),
+    Section::new("use_declaration", "The use declaration", use_declaration),
+    Section::new("super_and_self", "super and self", super_and_self),
+];
 
 mod my_mod {
     fn private_function() {
@@ -82,7 +87,7 @@ fn visibility() {
     pub(crate) items can be called from anywhere in the same crate
      */
     my_mod::public_function_in_crate();
-    // called `my_mod::nested::function()`
+    // called `my_mod::public_function_in_crate()`
 
     /*
     pub(in path) items
@@ -1,3 +1,9 @@
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("intro", "Crates", intro),
+];
+
 fn intro() {
     /*
     A crate is a compilation unit in Rust.
@@ -1,3 +1,12 @@
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("intro", "Cargo", intro),
+    Section::new("dependencies", "Dependencies", dependencies),
+    Section::new("conventions", "Conventions", conventions),
+    Section::new("testing", "Testing", testing),
+];
+
 fn intro() {
     /*
     `cargo` is the official Rust package management tool.
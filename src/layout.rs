@@ -0,0 +1,309 @@
+use std::any;
+use std::fmt;
+use std::mem::{self, offset_of};
+use std::ops::Range;
+
+use crate::a_hello_world::Person;
+use crate::c_custom_types::{LinkedList, Point, Rectangle};
+use crate::o_scoping_rules::Book;
+
+/*
+Where the bytes of a type go, beyond the single number `mem::size_of_val`
+prints in `b_primitives` and `e_rust`: the size and the alignment, the offset
+of every field and the padding the alignment leaves between and after them,
+drawn as a diagram with a letter for every byte of a field and `.` for
+padding:
+
+    Person: 24 bytes, aligned to 8
+       0  AAAAAAAA
+       8  AAAAAAAA
+      16  B.......
+      A  name     &str  0..16
+      B  age      u8    16..17
+      .  padding        17..24
+
+The compiler may reorder the fields of a struct without `#[repr(C)]` to save
+padding, so the fields are kept in the order of their offsets.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Layout {
+    pub name: String,
+    pub size: usize,
+    pub align: usize,
+    pub fields: Vec<Field>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    pub name: String,
+    pub type_name: String,
+    pub offset: usize,
+    pub size: usize,
+    pub align: usize,
+}
+
+impl Field {
+    pub fn bytes(&self) -> Range<usize> {
+        self.offset..self.offset + self.size
+    }
+}
+
+const BYTES_PER_ROW: usize = 8;
+
+impl Layout {
+    /*
+    The size and alignment of `T`, without fields until they are added with
+    `field`.
+     */
+    pub fn of<T>() -> Layout {
+        Layout {
+            name: type_name::<T>(),
+            size: mem::size_of::<T>(),
+            align: mem::align_of::<T>(),
+            fields: Vec::new(),
+        }
+    }
+
+    pub fn named(mut self, name: &str) -> Layout {
+        self.name = name.to_owned();
+        self
+    }
+
+    /*
+    Adds a field of type `F` starting at `offset`, as `mem::offset_of!`
+    gives it for a struct.
+     */
+    pub fn field<F>(mut self, name: &str, offset: usize) -> Layout {
+        let field = Field {
+            name: name.to_owned(),
+            type_name: type_name::<F>(),
+            offset,
+            size: mem::size_of::<F>(),
+            align: mem::align_of::<F>(),
+        };
+        assert!(field.bytes().end <= self.size, "`{}` does not fit into the {} bytes of `{}`", name, self.size, self.name);
+        self.fields.push(field);
+        self.fields.sort_by_key(|field| field.offset);
+        self
+    }
+
+    /*
+    The bytes no field covers. For a variant of an enum they also hold the
+    tag, unless the enum keeps it in a niche.
+     */
+    pub fn padding(&self) -> Vec<Range<usize>> {
+        let mut padding = Vec::new();
+        let mut end = 0;
+        for field in &self.fields {
+            if field.offset > end {
+                padding.push(end..field.offset);
+            }
+            end = end.max(field.bytes().end);
+        }
+        if self.size > end {
+            padding.push(end..self.size);
+        }
+        padding
+    }
+
+    pub fn diagram(&self) -> String {
+        let letters: Vec<char> = ('A'..='Z').chain('a'..='z').collect();
+        let letter = |index: usize| letters.get(index).copied().unwrap_or('#');
+
+        let mut bytes = vec!['.'; self.size];
+        for (index, field) in self.fields.iter().enumerate() {
+            for byte in &mut bytes[field.bytes()] {
+                *byte = letter(index);
+            }
+        }
+
+        let mut diagram = format!("{}: {}, aligned to {}\n", self.name, plural(self.size, "byte"), self.align);
+        let offset_width = self.size.saturating_sub(1).to_string().len().max(2) + 2;
+        for (row, chunk) in bytes.chunks(BYTES_PER_ROW).enumerate() {
+            let chunk: String = chunk.iter().collect();
+            diagram += &format!("{:>width$}  {}\n", row * BYTES_PER_ROW, chunk, width = offset_width);
+        }
+
+        let mut legend: Vec<(char, &str, &str, Range<usize>)> = self.fields.iter()
+            .enumerate()
+            .map(|(index, field)| (letter(index), field.name.as_str(), field.type_name.as_str(), field.bytes()))
+            .collect();
+        legend.extend(self.padding().into_iter().map(|bytes| ('.', "padding", "", bytes)));
+        legend.sort_by_key(|(_, _, _, bytes)| bytes.start);
+        let name_width = legend.iter().map(|(_, name, _, _)| name.chars().count()).max().unwrap_or(0);
+        let type_width = legend.iter().map(|(_, _, type_name, _)| type_name.chars().count()).max().unwrap_or(0);
+        for (letter, name, type_name, bytes) in legend {
+            let line = format!(
+                "  {}  {:name_width$}  {:type_width$}  {}..{}",
+                letter,
+                name,
+                type_name,
+                bytes.start,
+                bytes.end,
+                name_width = name_width,
+                type_width = type_width
+            );
+            diagram += line.trim_end();
+            diagram.push('\n');
+        }
+        diagram
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.diagram())
+    }
+}
+
+/*
+Whether an enum with one empty variant, like `Option<T>` or `LinkedList`,
+is as big as the data of its other variant. It is when the data has values
+it can never take, a niche, like the null pointer for `Box` and `&`, or the
+invalid `char`s; the empty variant is then stored as one of them and the
+enum needs no tag.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Niche {
+    pub name: String,
+    pub empty: String,
+    pub payload: String,
+    pub size: usize,
+    pub payload_size: usize,
+}
+
+impl Niche {
+    pub fn option<T>() -> Niche {
+        Niche::of::<Option<T>, T>("None")
+    }
+
+    /*
+    For an enum `E` whose empty variant is `empty` and whose other variant
+    holds a `P`.
+     */
+    pub fn of<E, P>(empty: &str) -> Niche {
+        Niche {
+            name: type_name::<E>(),
+            empty: empty.to_owned(),
+            payload: type_name::<P>(),
+            size: mem::size_of::<E>(),
+            payload_size: mem::size_of::<P>(),
+        }
+    }
+
+    pub fn is_used(&self) -> bool {
+        self.size == self.payload_size
+    }
+}
+
+impl fmt::Display for Niche {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_used() {
+            write!(
+                f,
+                "`{}` is {}, as big as `{}`: `{}` is stored in a niche",
+                self.name,
+                plural(self.size, "byte"),
+                self.payload,
+                self.empty
+            )
+        } else {
+            write!(
+                f,
+                "`{}` is {}, {} more than `{}` for the tag",
+                self.name,
+                plural(self.size, "byte"),
+                self.size - self.payload_size,
+                self.payload
+            )
+        }
+    }
+}
+
+macro_rules! layout {
+    ($type:ty { $($field:ident: $field_type:ty),* $(,)? }) => {
+        Layout::of::<$type>()$(.field::<$field_type>(stringify!($field), offset_of!($type, $field)))*
+    };
+}
+
+/*
+The layouts of the structs of the chapters, and of the `Node` of
+`LinkedList`.
+ */
+pub fn layouts() -> Vec<Layout> {
+    vec![
+        layout!(Point { x: f32, y: f32 }),
+        layout!(Rectangle { top_left: Point, bottom_right: Point }),
+        layout!(Person<'static> { name: &str, age: u8 }),
+        layout!(Book { author: &str, title: &str, year: u32 }),
+        linked_list_node(),
+    ]
+}
+
+pub fn niches() -> Vec<Niche> {
+    vec![
+        Niche::option::<Box<LinkedList<u32>>>(),
+        Niche::of::<LinkedList<u32>, (u32, Box<LinkedList<u32>>)>("Nil"),
+        Niche::option::<&str>(),
+        Niche::option::<char>(),
+        Niche::option::<u32>(),
+        Niche::option::<Point>(),
+    ]
+}
+
+/*
+Finds a layout by the name of its type, ignoring case, like `book` or
+`linkedlist::node`.
+ */
+pub fn find(name: &str) -> Option<Layout> {
+    layouts().into_iter().find(|layout| layout.name.eq_ignore_ascii_case(name))
+}
+
+/*
+`offset_of!` does not reach into the variants of an enum, so the offsets of
+a node are measured on one.
+ */
+fn linked_list_node() -> Layout {
+    let node = LinkedList::Node(0, Box::new(LinkedList::Nil));
+    let LinkedList::Node(elem, tail) = &node else { unreachable!() };
+    Layout::of::<LinkedList<u32>>()
+        .named("LinkedList::Node")
+        .field::<u32>("0", offset(&node, elem))
+        .field::<Box<LinkedList<u32>>>("1", offset(&node, tail))
+}
+
+fn offset<T, F>(value: &T, field: &F) -> usize {
+    field as *const F as usize - value as *const T as usize
+}
+
+/*
+`any::type_name` without the paths and the erased lifetimes, so
+`core::option::Option<alloc::boxed::Box<rust_by_example::c_custom_types::LinkedList<u32>>>`
+becomes `Option<Box<LinkedList<u32>>>` and `Person<'_>` becomes `Person`.
+ */
+pub fn type_name<T: ?Sized>() -> String {
+    let full = any::type_name::<T>();
+    let mut name = String::with_capacity(full.len());
+    let mut path_start = 0;
+    let mut chars = full.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == ':' && chars.peek() == Some(&':') {
+            chars.next();
+            name.truncate(path_start);
+        } else {
+            if !(c.is_alphanumeric() || c == '_') {
+                path_start = name.len() + c.len_utf8();
+            }
+            name.push(c);
+        }
+    }
+    name.replace("<'_>", "").replace("'_, ", "")
+}
+
+fn plural(count: usize, unit: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
@@ -0,0 +1,42 @@
+#[macro_use]
+mod macros;
+
+pub mod a_hello_world;
+pub mod b_primitives;
+pub mod c_custom_types;
+pub mod d_variable_bindings;
+pub mod e_rust;
+pub mod f_conversion;
+pub mod g_expressions;
+pub mod h_flow_of_control;
+pub mod i_functions;
+pub mod j_modules;
+pub mod k_crates;
+pub mod l_cargo;
+pub mod m_attributes;
+pub mod n_generics;
+pub mod o_scoping_rules;
+
+pub mod bits;
+pub mod book;
+pub mod bus;
+pub mod chapter;
+pub mod color;
+pub mod format;
+pub mod geo;
+pub mod golden;
+pub mod json;
+pub mod layout;
+pub mod list;
+pub mod literal;
+pub mod log;
+pub mod output;
+pub mod persistent;
+pub mod pretty;
+pub mod registry;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod source;
+pub mod table;
+pub mod web_event;
@@ -0,0 +1,145 @@
+use std::fmt::{self, Display, Formatter};
+
+/*
+Displays any iterable of `Display` items as a list, so that a newtype around
+a vector like `a_hello_world::MyList` does not need its own loop:
+
+    List::new(&vec![1, 2, 3]).indexed()              [0: 1, 1: 2, 2: 3]
+    List::new(1..=100).limit(3)                      [1, 2, 3, ...and 97 more]
+    List::new(0..).limit(3)                          [0, 1, 2, ...and over 1000 more]
+    List::new(&words).separator(" | ").brackets("", "")
+
+The items are borrowed through `Clone`, which references and ranges are, so
+the list can be displayed any number of times. A list of collections is
+shown with `nested`, which displays each inner collection as a list with
+the same options.
+ */
+#[derive(Clone, Debug, PartialEq)]
+struct Style {
+    indexed: bool,
+    separator: String,
+    open: String,
+    close: String,
+    limit: Option<usize>,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Style {
+            indexed: false,
+            separator: ", ".to_owned(),
+            open: "[".to_owned(),
+            close: "]".to_owned(),
+            limit: None,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct List<I> {
+    items: I,
+    style: Style,
+}
+
+/*
+A list whose items are collections themselves, see `List::nested`.
+ */
+#[derive(Clone, Debug)]
+pub struct Nested<I>(List<I>);
+
+impl<I> List<I> where I: IntoIterator + Clone {
+    pub fn new(items: I) -> List<I> {
+        List { items, style: Style::default() }
+    }
+
+    /*
+    Labels every item with its position, from 0, like `0: 1`.
+     */
+    pub fn indexed(mut self) -> List<I> {
+        self.style.indexed = true;
+        self
+    }
+
+    pub fn separator(mut self, separator: &str) -> List<I> {
+        self.style.separator = separator.to_owned();
+        self
+    }
+
+    pub fn brackets(mut self, open: &str, close: &str) -> List<I> {
+        self.style.open = open.to_owned();
+        self.style.close = close.to_owned();
+        self
+    }
+
+    /*
+    Shows at most `limit` items and then counts the others, like
+    `...and 7 more`. Counting stops past `MAX_COUNTED`, so that a long or
+    endless iterator is not run to its end.
+     */
+    pub fn limit(mut self, limit: usize) -> List<I> {
+        self.style.limit = Some(limit);
+        self
+    }
+
+    /*
+    Displays every item as a list itself, with the same options at both
+    levels: `[[1, 2], [3]]`. Deeper nesting composes by mapping the items to
+    `List`s or `Nested`s of their own.
+     */
+    pub fn nested(self) -> Nested<I> {
+        Nested(self)
+    }
+}
+
+/*
+How many items past the limit are counted before giving up.
+ */
+pub const MAX_COUNTED: usize = 1000;
+
+impl Style {
+    fn write<T, F>(&self, f: &mut Formatter<'_>, items: T, mut item: F) -> fmt::Result
+        where T: IntoIterator, F: FnMut(&mut Formatter<'_>, T::Item) -> fmt::Result {
+        let mut items = items.into_iter();
+        write!(f, "{}", self.open)?;
+        for index in 0.. {
+            if self.limit == Some(index) {
+                let more = items.take(MAX_COUNTED + 1).count();
+                if more > 0 {
+                    if index > 0 {
+                        write!(f, "{}", self.separator)?;
+                    }
+                    if more > MAX_COUNTED {
+                        write!(f, "...and over {} more", MAX_COUNTED)?;
+                    } else {
+                        write!(f, "...and {} more", more)?;
+                    }
+                }
+                break;
+            }
+            let Some(next) = items.next() else { break };
+
+            if index > 0 {
+                write!(f, "{}", self.separator)?;
+            }
+            if self.indexed {
+                write!(f, "{}: ", index)?;
+            }
+            item(f, next)?;
+        }
+        write!(f, "{}", self.close)
+    }
+}
+
+impl<I> Display for List<I> where I: IntoIterator + Clone, I::Item: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.style.write(f, self.items.clone(), |f, item| write!(f, "{}", item))
+    }
+}
+
+impl<I> Display for Nested<I>
+    where I: IntoIterator + Clone, I::Item: IntoIterator + Clone, <I::Item as IntoIterator>::Item: Display {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let List { items, style } = &self.0;
+        style.write(f, items.clone(), |f, inner| write!(f, "{}", List { items: inner, style: style.clone() }))
+    }
+}
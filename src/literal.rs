@@ -0,0 +1,366 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bits::{self, Int, IntType, INT_TYPES};
+
+/*
+Numeric literals as Rust writes them, like `1_000_000u32`, `5i32`,
+`65.4321_f32`, `0xffu8`, `1e-7` or `4294967296i64`, so that configuration
+files can use the numbers of the examples. A literal gets the type of its
+suffix, the type the caller expects, or else `i32` for integers and `f64`
+for floats, as inference does for unsuffixed literals. A leading `-` is
+allowed too.
+
+A literal that does not fit its type is reported the way the
+`overflowing_literals` lint does, along with the value it gets when the lint
+is allowed, like at the top of `e_rust`: `256u8` wraps to `0` and `1e40f32`
+becomes infinite.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Number {
+    Int(Int),
+    F32(f32),
+    F64(f64),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum NumberType {
+    Int(IntType),
+    F32,
+    F64,
+}
+
+impl Number {
+    pub fn number_type(&self) -> NumberType {
+        match self {
+            Number::Int(int) => NumberType::Int(int.int_type()),
+            Number::F32(_) => NumberType::F32,
+            Number::F64(_) => NumberType::F64,
+        }
+    }
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Int(int) => write!(f, "{}{}", int, int.int_type()),
+            Number::F32(float) => write!(f, "{:?}f32", float),
+            Number::F64(float) => write!(f, "{:?}f64", float),
+        }
+    }
+}
+
+impl NumberType {
+    pub fn name(self) -> &'static str {
+        match self {
+            NumberType::Int(int_type) => int_type.name(),
+            NumberType::F32 => "f32",
+            NumberType::F64 => "f64",
+        }
+    }
+
+    fn is_float(self) -> bool {
+        matches!(self, NumberType::F32 | NumberType::F64)
+    }
+}
+
+impl fmt::Display for NumberType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for NumberType {
+    type Err = Error;
+
+    fn from_str(name: &str) -> Result<NumberType, Error> {
+        match name {
+            "f32" => Ok(NumberType::F32),
+            "f64" => Ok(NumberType::F64),
+            _ => INT_TYPES.into_iter()
+                .find(|int_type| int_type.name() == name)
+                .map(NumberType::Int)
+                .ok_or_else(|| Error::Type(format!("unknown numeric type `{}`", name))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Syntax { column: usize, message: String },
+    Type(String),
+    /*
+    `wrapped` is the value the literal has when `overflowing_literals` is
+    allowed.
+     */
+    OutOfRange { literal: String, number_type: NumberType, wrapped: Number },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax { column, message } => write!(f, "column {}: {}", column, message),
+            Error::Type(message) => write!(f, "{}", message),
+            Error::OutOfRange { literal, number_type: NumberType::Int(int_type), .. } => write!(
+                f,
+                "literal out of range for `{0}`: the literal `{1}` does not fit into the type `{0}` whose range is `{2}..={3}`",
+                int_type,
+                literal,
+                Int::min(*int_type),
+                Int::max(*int_type)
+            ),
+            Error::OutOfRange { literal, number_type, .. } => write!(
+                f,
+                "literal out of range for `{0}`: the literal `{1}` does not fit into the type `{0}` and will be converted to `{0}::INFINITY`",
+                number_type,
+                literal
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl FromStr for Number {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Number, Error> {
+        parse(text)
+    }
+}
+
+pub fn parse(text: &str) -> Result<Number, Error> {
+    parse_literal(text, None)
+}
+
+/*
+Like `parse`, with unsuffixed literals taking the `expected` type as in
+`let x: u64 = 5;`. As in Rust an integer literal is not a float, so `5` is
+not an `f64` while `5.0` and `5f64` are.
+ */
+pub fn parse_as(text: &str, expected: NumberType) -> Result<Number, Error> {
+    parse_literal(text, Some(expected))
+}
+
+/*
+The parts of a literal: the sign, the radix, the digits without the prefix
+and the suffix, and the column where the suffix starts.
+ */
+struct Parts<'a> {
+    negative: bool,
+    radix: u32,
+    digits: &'a str,
+    digits_column: usize,
+    float: bool,
+    suffix: &'a str,
+    suffix_column: usize,
+}
+
+fn parse_literal(text: &str, expected: Option<NumberType>) -> Result<Number, Error> {
+    let parts = split(text)?;
+
+    let suffixed = match parts.suffix {
+        "" => None,
+        suffix => Some(suffix.parse::<NumberType>().map_err(|_| Error::Syntax {
+            column: parts.suffix_column,
+            message: format!("invalid suffix `{}` for {} literal", suffix, if parts.float { "float" } else { "number" }),
+        })?),
+    };
+    let float = parts.float || suffixed.is_some_and(NumberType::is_float);
+    if let Some(suffix) = suffixed {
+        if parts.float && !suffix.is_float() {
+            return Err(Error::Syntax { column: parts.suffix_column, message: format!("invalid suffix `{}` for float literal", suffix) });
+        }
+        if suffix.is_float() && parts.radix != 10 {
+            return Err(Error::Syntax {
+                column: parts.suffix_column,
+                message: format!("a float suffix like `{}` is only allowed on decimal literals", suffix),
+            });
+        }
+    }
+
+    let number_type = match (suffixed, expected) {
+        (Some(suffix), Some(expected)) if suffix != expected => {
+            return Err(Error::Type(format!("mismatched types: expected `{}`, found `{}`", expected, suffix)));
+        }
+        (Some(number_type), _) => number_type,
+        (None, Some(expected)) if expected.is_float() != float => {
+            let found = if float { "floating-point number" } else { "integer" };
+            return Err(Error::Type(format!("mismatched types: expected `{}`, found {}", expected, found)));
+        }
+        (None, Some(expected)) => expected,
+        (None, None) if float => NumberType::F64,
+        (None, None) => NumberType::Int(IntType::I32),
+    };
+
+    match number_type {
+        NumberType::Int(int_type) => {
+            if parts.negative && !int_type.signed() {
+                return Err(Error::Type(format!("cannot apply unary operator `-` to type `{}`", int_type)));
+            }
+            let magnitude = bits::parse_digits(parts.digits, parts.radix, parts.digits_column)
+                .map_err(syntax)?
+                .ok_or_else(|| Error::Syntax { column: parts.digits_column, message: "integer literal is too large".to_owned() })?;
+            Int::from_magnitude(parts.negative, magnitude, int_type).map(Number::Int).ok_or_else(|| {
+                let bits = if parts.negative { magnitude.wrapping_neg() } else { magnitude };
+                Error::OutOfRange {
+                    literal: text.trim().to_owned(),
+                    number_type,
+                    wrapped: Number::Int(Int::from_bits(int_type, bits)),
+                }
+            })
+        }
+        NumberType::F32 | NumberType::F64 => {
+            let digits: String = parts.digits.chars().filter(|&c| c != '_').collect();
+            let signed = if parts.negative { format!("-{}", digits) } else { digits };
+            let number = if number_type == NumberType::F32 {
+                Number::F32(signed.parse().expect("a valid float"))
+            } else {
+                Number::F64(signed.parse().expect("a valid float"))
+            };
+            let infinite = match number {
+                Number::F32(float) => float.is_infinite(),
+                Number::F64(float) => float.is_infinite(),
+                Number::Int(_) => false,
+            };
+            if infinite {
+                return Err(Error::OutOfRange { literal: text.trim().to_owned(), number_type, wrapped: number });
+            }
+            Ok(number)
+        }
+    }
+}
+
+fn syntax(error: bits::Error) -> Error {
+    match error {
+        bits::Error::Syntax { column, message } => Error::Syntax { column, message },
+        error => Error::Type(error.to_string()),
+    }
+}
+
+/*
+Splits a literal by the grammar of Rust, where the digits may contain `_`
+anywhere but at the start of a decimal literal, a fraction needs a digit
+after the `.` unless it ends the literal, and an exponent needs a digit.
+ */
+fn split(text: &str) -> Result<Parts<'_>, Error> {
+    let leading = text.len() - text.trim_start().len();
+    let text = text.trim();
+    let (negative, body) = match text.strip_prefix('-') {
+        Some(body) => (true, body),
+        None => (false, text),
+    };
+    let start = leading + usize::from(negative);
+    let column = |offset: usize| start + body[..offset].chars().count() + 1;
+    let bytes = body.as_bytes();
+
+    let (radix, prefix) = match body.get(..2) {
+        Some("0b") => (2, 2),
+        Some("0o") => (8, 2),
+        Some("0x") => (16, 2),
+        _ => (10, 0),
+    };
+    match bytes.get(prefix) {
+        Some(c) if c.is_ascii_digit() || prefix > 0 && (*c == b'_' || c.is_ascii_hexdigit()) => {}
+        Some(_) => return Err(Error::Syntax { column: column(prefix), message: format!("`{}` is not a number", text) }),
+        None => return Err(Error::Syntax { column: column(prefix), message: "expected digits".to_owned() }),
+    }
+
+    let is_digit = |c: u8| c == b'_' || if radix == 16 { c.is_ascii_hexdigit() } else { c.is_ascii_digit() };
+    let mut end = prefix;
+    while end < bytes.len() && is_digit(bytes[end]) {
+        end += 1;
+    }
+
+    let mut float = false;
+    if radix == 10 {
+        if bytes.get(end) == Some(&b'.') {
+            match bytes.get(end + 1) {
+                Some(c) if c.is_ascii_digit() => {
+                    end += 1;
+                    while end < bytes.len() && is_digit(bytes[end]) {
+                        end += 1;
+                    }
+                }
+                None => end += 1,
+                Some(_) => {
+                    return Err(Error::Syntax { column: column(end + 1), message: "expected digits after `.`".to_owned() });
+                }
+            }
+            float = true;
+        }
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let mut exponent = end + 1;
+            if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+                exponent += 1;
+            }
+            let digits_start = exponent;
+            while exponent < bytes.len() && is_digit(bytes[exponent]) {
+                exponent += 1;
+            }
+            if !body[digits_start..exponent].bytes().any(|c| c.is_ascii_digit()) {
+                return Err(Error::Syntax { column: column(end), message: "expected at least one digit in exponent".to_owned() });
+            }
+            end = exponent;
+            float = true;
+        }
+    }
+
+    Ok(Parts {
+        negative,
+        radix,
+        digits: &body[prefix..end],
+        digits_column: column(prefix),
+        float,
+        suffix: &body[end..],
+        suffix_column: column(end),
+    })
+}
+
+macro_rules! number_into {
+    ($($primitive:ty => $variant:ident,)*) => {
+        $(
+            impl TryFrom<Number> for $primitive {
+                type Error = Error;
+
+                fn try_from(number: Number) -> Result<$primitive, Error> {
+                    match number {
+                        Number::Int(int) if int.int_type() == IntType::$variant => Ok(int.bits() as $primitive),
+                        number => Err(Error::Type(format!(
+                            "mismatched types: expected `{}`, found `{}`",
+                            stringify!($primitive),
+                            number.number_type()
+                        ))),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+number_into! {
+    u8 => U8, u16 => U16, u32 => U32, u64 => U64, u128 => U128, usize => Usize,
+    i8 => I8, i16 => I16, i32 => I32, i64 => I64, i128 => I128, isize => Isize,
+}
+
+impl TryFrom<Number> for f32 {
+    type Error = Error;
+
+    fn try_from(number: Number) -> Result<f32, Error> {
+        match number {
+            Number::F32(float) => Ok(float),
+            number => Err(Error::Type(format!("mismatched types: expected `f32`, found `{}`", number.number_type()))),
+        }
+    }
+}
+
+impl TryFrom<Number> for f64 {
+    type Error = Error;
+
+    fn try_from(number: Number) -> Result<f64, Error> {
+        match number {
+            Number::F64(float) => Ok(float),
+            number => Err(Error::Type(format!("mismatched types: expected `f64`, found `{}`", number.number_type()))),
+        }
+    }
+}
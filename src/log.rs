@@ -0,0 +1,341 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::output::{self, Stream};
+
+/*
+A small logging facade. The `error!`, `warn!`, `info!`, `debug!` and
+`trace!` macros of this crate make a record with the level, the target, that
+is the name of the chapter like `hello_world` or of the module like `runner`,
+and the time, and hand it to the logger installed with `install`. The logger
+keeps the records its filters let through and writes them to its sinks as
+
+    [ERROR hello_world] Error print
+    [2026-10-18T09:30:00.123Z DEBUG runner] hello_world::intro ran in 41.2µs
+
+When no logger is installed, records of `Info` and above go to `Stderr`.
+
+The log carries diagnostics only. What the chapters print with `println!` is
+the lesson itself, the output `verify` checks and `export` shows, so it
+stays on `output`; the one diagnostic of the chapters, the `Error print` of
+`hello_world::format_print`, is an `error!`, and the runner logs every
+section it runs at `Debug` and `Trace`.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    pub fn name(self) -> &'static str {
+        match self {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+}
+
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Level, String> {
+        [Level::Error, Level::Warn, Level::Info, Level::Debug, Level::Trace].into_iter()
+            .find(|level| level.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("unknown log level `{}`, expected `error`, `warn`, `info`, `debug`, `trace` or `off`", name))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record {
+    pub level: Level,
+    pub target: String,
+    pub time: SystemTime,
+    pub message: String,
+}
+
+impl Record {
+    pub fn format(&self, timestamps: bool) -> String {
+        if timestamps {
+            format!("[{} {} {}] {}", timestamp(self.time), self.level, self.target, self.message)
+        } else {
+            format!("[{} {}] {}", self.level, self.target, self.message)
+        }
+    }
+}
+
+/*
+Where the records go. Each sink gets the record along with the line the
+logger formatted for it.
+ */
+pub trait Sink {
+    fn write(&mut self, record: &Record, line: &str) -> io::Result<()>;
+}
+
+/*
+Standard error through `output`, so that the records of a section are
+captured with the rest of its output while the runner captures it.
+ */
+pub struct Stderr;
+
+impl Sink for Stderr {
+    fn write(&mut self, _: &Record, line: &str) -> io::Result<()> {
+        output::print(Stream::Stderr, format_args!("{}\n", line));
+        Ok(())
+    }
+}
+
+pub struct FileSink {
+    file: File,
+}
+
+impl FileSink {
+    /*
+    Appends to the file, creating it when it does not exist.
+     */
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<FileSink> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(FileSink { file })
+    }
+}
+
+impl Sink for FileSink {
+    fn write(&mut self, _: &Record, line: &str) -> io::Result<()> {
+        writeln!(self.file, "{}", line)
+    }
+}
+
+/*
+Keeps the records in memory for tests. Clones share the same records, so
+one clone can be installed while the other one reads them.
+ */
+#[derive(Clone, Default)]
+pub struct Memory {
+    records: Rc<RefCell<Vec<Record>>>,
+}
+
+impl Memory {
+    pub fn new() -> Memory {
+        Memory::default()
+    }
+
+    pub fn records(&self) -> Vec<Record> {
+        self.records.borrow().clone()
+    }
+
+    pub fn messages(&self) -> Vec<String> {
+        self.records.borrow().iter().map(|record| record.message.clone()).collect()
+    }
+}
+
+impl Sink for Memory {
+    fn write(&mut self, record: &Record, _: &str) -> io::Result<()> {
+        self.records.borrow_mut().push(record.clone());
+        Ok(())
+    }
+}
+
+/*
+A level of `None` turns logging off, for every target or for one.
+ */
+pub struct Logger {
+    level: Option<Level>,
+    targets: Vec<(String, Option<Level>)>,
+    timestamps: bool,
+    sinks: Vec<Box<dyn Sink>>,
+}
+
+impl Default for Logger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Logger {
+    /*
+    Lets records of `Info` and above through to no sink at all, until some
+    are added with `sink`.
+     */
+    pub fn new() -> Logger {
+        Logger { level: Some(Level::Info), targets: Vec::new(), timestamps: false, sinks: Vec::new() }
+    }
+
+    pub fn level(mut self, level: Option<Level>) -> Logger {
+        self.level = level;
+        self
+    }
+
+    /*
+    Overrides the level for one target, like `hello_world`.
+     */
+    pub fn target(mut self, target: &str, level: Option<Level>) -> Logger {
+        self.targets.retain(|(name, _)| name != target);
+        self.targets.push((target.to_owned(), level));
+        self
+    }
+
+    /*
+    Applies filters written as `debug`, `off` or `hello_world=trace,warn`,
+    comma-separated, where a bare level sets the level of every target.
+     */
+    pub fn filters(mut self, spec: &str) -> Result<Logger, String> {
+        for filter in spec.split(',').map(str::trim).filter(|filter| !filter.is_empty()) {
+            let parse = |name: &str| match name {
+                "off" => Ok(None),
+                name => name.parse().map(Some),
+            };
+            self = match filter.split_once('=') {
+                Some((target, level)) => self.target(target.trim(), parse(level.trim())?),
+                None => self.level(parse(filter)?),
+            };
+        }
+        Ok(self)
+    }
+
+    pub fn timestamps(mut self, timestamps: bool) -> Logger {
+        self.timestamps = timestamps;
+        self
+    }
+
+    pub fn sink<S: Sink + 'static>(mut self, sink: S) -> Logger {
+        self.sinks.push(Box::new(sink));
+        self
+    }
+
+    pub fn enabled(&self, level: Level, target: &str) -> bool {
+        let threshold = self.targets.iter()
+            .find(|(name, _)| name == target)
+            .map_or(self.level, |&(_, level)| level);
+        threshold.is_some_and(|threshold| level <= threshold)
+    }
+
+    pub fn log(&mut self, record: &Record) {
+        if !self.enabled(record.level, &record.target) {
+            return;
+        }
+        let line = record.format(self.timestamps);
+        for sink in &mut self.sinks {
+            if let Err(err) = sink.write(record, &line) {
+                panic!("failed writing a log record: {}", err);
+            }
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Logger>> = const { RefCell::new(None) };
+}
+
+struct Restore(Option<Logger>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/*
+Runs `f` with `logger` receiving the records, and puts back the previous
+logger afterwards, also when `f` panics.
+ */
+pub fn install<F, R>(logger: Logger, f: F) -> R
+    where F: FnOnce() -> R {
+    let previous = CURRENT.with(|current| current.borrow_mut().replace(logger));
+    let _restore = Restore(previous);
+    f()
+}
+
+/*
+The target of a module path: the module below the crate root, without the
+letter that orders the chapters, so `rust_by_example::a_hello_world::inner`
+becomes `hello_world`.
+ */
+pub fn target(module_path: &str) -> &str {
+    let module = module_path.split("::").nth(1).unwrap_or(module_path);
+    match module.as_bytes() {
+        [letter, b'_', ..] if letter.is_ascii_lowercase() => &module[2..],
+        _ => module,
+    }
+}
+
+pub fn log(level: Level, module_path: &str, args: fmt::Arguments) {
+    let target = target(module_path);
+    let enabled = CURRENT.with(|current| match current.borrow().as_ref() {
+        Some(logger) => logger.enabled(level, target),
+        None => level <= Level::Info,
+    });
+    if !enabled {
+        return;
+    }
+
+    /*
+    Formatting before taking the logger lets `Display` impls log too.
+     */
+    let record = Record { level, target: target.to_owned(), time: SystemTime::now(), message: fmt::format(args) };
+    let logged = CURRENT.with(|current| match current.borrow_mut().as_mut() {
+        Some(logger) => {
+            logger.log(&record);
+            true
+        }
+        None => false,
+    });
+    if !logged {
+        Logger::new().sink(Stderr).log(&record);
+    }
+}
+
+/*
+UTC in the format of RFC 3339 with milliseconds, like
+`2026-10-18T09:30:00.123Z`.
+ */
+pub fn timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (year, month, day) = civil_date((seconds / 86_400) as i64);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/*
+The proleptic Gregorian date of a day counted from 1970-01-01, after Howard
+Hinnant's `civil_from_days`.
+ */
+fn civil_date(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 { shifted_month + 3 } else { shifted_month - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -1,8 +1,10 @@
-pub fn main() {
-    intro();
-    dead_code();
-    cfg();
-}
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("intro", "Attributes", intro),
+    Section::new("dead_code", "dead_code", dead_code),
+    Section::new("cfg", "cfg", cfg),
+];
 
 fn intro() {
     /*
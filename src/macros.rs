@@ -0,0 +1,79 @@
+/*
+These shadow the `std` macros for every module `lib.rs` declares after this
+one, so that the chapters print through `output` and the runner decides
+where it goes. The chapters are compiled on their own with this file in
+`tests/compile_fail.rs`, so it holds nothing but macros.
+ */
+macro_rules! print {
+    ($($arg:tt)*) => {
+        $crate::output::print($crate::output::Stream::Stdout, format_args!($($arg)*))
+    };
+}
+
+macro_rules! println {
+    () => {
+        print!("\n")
+    };
+    ($($arg:tt)*) => {
+        print!("{}\n", format_args!($($arg)*))
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! eprint {
+    ($($arg:tt)*) => {
+        $crate::output::print($crate::output::Stream::Stderr, format_args!($($arg)*))
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! eprintln {
+    () => {
+        eprint!("\n")
+    };
+    ($($arg:tt)*) => {
+        eprint!("{}\n", format_args!($($arg)*))
+    };
+}
+
+/*
+The logging macros, which hand a record to the logger installed with
+`log::install` under the chapter or module they are used in.
+ */
+macro_rules! log {
+    ($level:ident, $($arg:tt)*) => {
+        $crate::log::log($crate::log::Level::$level, module_path!(), format_args!($($arg)*))
+    };
+}
+
+macro_rules! error {
+    ($($arg:tt)*) => {
+        log!(Error, $($arg)*)
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! warn {
+    ($($arg:tt)*) => {
+        log!(Warn, $($arg)*)
+    };
+}
+
+#[allow(unused_macros)]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        log!(Info, $($arg)*)
+    };
+}
+
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        log!(Debug, $($arg)*)
+    };
+}
+
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        log!(Trace, $($arg)*)
+    };
+}
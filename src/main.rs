@@ -1,32 +1,72 @@
-mod a_hello_world;
-mod b_primitives;
-mod c_custom_types;
-mod d_variable_bindings;
-mod e_rust;
-mod f_conversion;
-mod g_expressions;
-mod h_flow_of_control;
-mod i_functions;
-mod j_modules;
-mod k_crates;
-mod l_cargo;
-mod m_attributes;
-mod n_generics;
-mod o_scoping_rules;
+mod cli;
+
+use std::io;
+use std::process;
+
+use cli::{Command, Log};
+use rust_by_example::log::{self, FileSink, Logger, Stderr};
+use rust_by_example::output::Output;
+use rust_by_example::repl;
 
 fn main() {
-    // a_hello_world::main();
-    // b_primitives::main();
-    // c_custom_types::main();
-    // d_variable_bindings::main();
-    // e_rust::main();
-    // f_conversion::main();
-    // g_expressions::main();
-    // h_flow_of_control::main();
-    // i_functions::main();
-    // j_modules::main();
-    // m_attributes::main();
-    // n_generics::main();
-    o_scoping_rules::main();
+    match cli::parse(std::env::args().skip(1)) {
+        Ok(Command::List) => cli::list(),
+        Ok(Command::ListSections(entries)) => cli::list_sections(&entries),
+        Ok(Command::Run { entries, quiet, output, log }) => {
+            let output = match output {
+                Some(path) => Output::file(&path).unwrap_or_else(|err| {
+                    eprintln!("error: cannot write to `{}`: {}", path, err);
+                    process::exit(1);
+                }),
+                None => Output::inherit(),
+            };
+            match log::install(logger(log), || cli::run(&entries, quiet, output)) {
+                Ok(true) => {}
+                Ok(false) => process::exit(101),
+                Err(err) => {
+                    eprintln!("error: {}", err);
+                    process::exit(1);
+                }
+            }
+        }
+        Ok(Command::Verify { entries, format, log }) => {
+            if !log::install(logger(log), || cli::verify(&entries, format)) {
+                process::exit(1);
+            }
+        }
+        Ok(Command::Export { entries, dir, format, log }) => {
+            if let Err(err) = log::install(logger(log), || cli::export(&entries, &dir, format)) {
+                eprintln!("error: cannot write the book to `{}`: {}", dir, err);
+                process::exit(1);
+            }
+        }
+        Ok(Command::Repl(log)) => {
+            if let Err(err) = log::install(logger(log), || repl::run(io::stdin().lock(), Output::inherit())) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+        Ok(Command::Bits { expression, int_type }) => {
+            if let Err(err) = cli::bits(&expression, int_type) {
+                eprintln!("error: {}", err);
+                process::exit(1);
+            }
+        }
+        Ok(Command::Layout { layouts, niches }) => cli::layout(&layouts, niches),
+        Ok(Command::Help) => println!("{}", cli::USAGE),
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            process::exit(2);
+        }
+    }
+}
+
+fn logger(log: Log) -> Logger {
+    match log.file {
+        Some(path) => log.logger.sink(FileSink::open(&path).unwrap_or_else(|err| {
+            eprintln!("error: cannot write the log to `{}`: {}", path, err);
+            process::exit(1);
+        })),
+        None => log.logger.sink(Stderr),
+    }
 }
-// rustc main.rs
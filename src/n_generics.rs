@@ -1,14 +1,18 @@
-pub fn main() {
-    generics_functions();
-    generics_implementations();
-    generics_traits();
-    generics_bounds();
-    generics_multiple_bounds();
-    generics_where_clauses();
-    generics_new_type_idiom();
-    generics_associated_items();
-    generics_phantom_type_params();
-}
+#![allow(dead_code)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("generics_functions", "Functions", generics_functions),
+    Section::new("generics_implementations", "Implementation", generics_implementations),
+    Section::new("generics_traits", "Traits", generics_traits),
+    Section::new("generics_bounds", "Bounds", generics_bounds),
+    Section::new("generics_multiple_bounds", "Multiple bounds", generics_multiple_bounds),
+    Section::new("generics_where_clauses", "Where clauses", generics_where_clauses),
+    Section::new("generics_new_type_idiom", "New Type Idiom", generics_new_type_idiom),
+    Section::new("generics_associated_items", "Associated items", generics_associated_items),
+    Section::new("generics_phantom_type_params", "Phantom type parameters", generics_phantom_type_params),
+];
 
 fn generics_functions() {
     struct A;          // Concrete type `A`.
@@ -1,24 +1,30 @@
-pub fn main() {
-    raii();
-    raii_destructor();
-    ownership_and_moves();
-    ownership_and_movers_of_mutability();
-    partial_moves();
-    borrowing();
-    borrowing_mutability();
-    borrowing_aliasing();
-    borrowing_the_ref_pattern();
-    lifetime();
-    lifetime_explicit_annotation();
-    lifetime_functions();
-    lifetime_methods();
-    lifetime_structs();
-    lifetime_traits();
-    lifetime_bounds();
-    lifetime_coercion();
-    lifetime_static();
-    lifetime_elision();
-}
+#![allow(dead_code, unused_variables)]
+#![allow(clippy::extra_unused_lifetimes, clippy::needless_lifetimes, clippy::toplevel_ref_arg)]
+
+use crate::chapter::Section;
+
+pub const SECTIONS: &[Section] = &[
+    Section::new("intro", "Scoping rules", intro),
+    Section::new("raii", "RAII", raii),
+    Section::new("raii_destructor", "Destructor", raii_destructor),
+    Section::new("ownership_and_moves", "Ownership and moves", ownership_and_moves),
+    Section::new("ownership_and_movers_of_mutability", "Mutability of moved values", ownership_and_movers_of_mutability),
+    Section::new("partial_moves", "Partial moves", partial_moves),
+    Section::new("borrowing", "Borrowing", borrowing),
+    Section::new("borrowing_mutability", "Mutability", borrowing_mutability),
+    Section::new("borrowing_aliasing", "Aliasing", borrowing_aliasing),
+    Section::new("borrowing_the_ref_pattern", "The ref pattern", borrowing_the_ref_pattern),
+    Section::new("lifetime", "Lifetimes", lifetime),
+    Section::new("lifetime_explicit_annotation", "Explicit annotation", lifetime_explicit_annotation),
+    Section::new("lifetime_functions", "Functions", lifetime_functions),
+    Section::new("lifetime_methods", "Methods", lifetime_methods),
+    Section::new("lifetime_structs", "Structs", lifetime_structs),
+    Section::new("lifetime_traits", "Traits", lifetime_traits),
+    Section::new("lifetime_bounds", "Bounds", lifetime_bounds),
+    Section::new("lifetime_coercion", "Coercion", lifetime_coercion),
+    Section::new("lifetime_static", "Static", lifetime_static),
+    Section::new("lifetime_elision", "Elision", lifetime_elision),
+];
 
 fn intro() {
     /*
@@ -191,6 +197,13 @@ fn borrowing() {
     // Destroying box that contains 5
 }
 
+#[derive(Clone, Copy)]
+pub struct Book {
+    pub author: &'static str, // `&'static str` is a reference to a string allocated in read only memory
+    pub title: &'static str, // `&'static str` is a reference to a string allocated in read only memory
+    pub year: u32,
+}
+
 fn borrowing_mutability() {
     /*
     Mutable data can be mutably borrowed using `&mut T`. This is called
@@ -199,14 +212,6 @@ fn borrowing_mutability() {
     and the borrower can read the data but not modify it
      */
 
-    #[allow(dead_code)]
-    #[derive(Clone, Copy)]
-    struct Book {
-        author: &'static str, // `&'static str` is a reference to a string allocated in read only memory
-        title: &'static str, // `&'static str` is a reference to a string allocated in read only memory
-        year: u32,
-    }
-
     fn borrow_book(book: &Book) { // Takes a reference to a book
         println!("I immutably borrowed {} - {} edition", book.title, book.year);
     }
@@ -0,0 +1,173 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::Path;
+use std::rc::Rc;
+
+/*
+The sink that sections write to. The `print!`, `println!`, `eprint!` and
+`eprintln!` macros of this crate shadow the `std` ones and write to the
+output installed by the runner with `redirect`, falling back to the real
+stdout and stderr when none is installed.
+ */
+pub struct Output {
+    stdout: Box<dyn Write>,
+    stderr: Box<dyn Write>,
+}
+
+impl Output {
+    pub fn new<O, E>(stdout: O, stderr: E) -> Output
+        where O: Write + 'static, E: Write + 'static {
+        Output { stdout: Box::new(stdout), stderr: Box::new(stderr) }
+    }
+
+    pub fn inherit() -> Output {
+        Output::new(io::stdout(), io::stderr())
+    }
+
+    /*
+    Both streams go to the same file, in the order they are written.
+     */
+    pub fn file<P: AsRef<Path>>(path: P) -> io::Result<Output> {
+        let stdout = File::create(path)?;
+        let stderr = stdout.try_clone()?;
+        Ok(Output::new(stdout, stderr))
+    }
+
+    pub fn write(&mut self, stream: Stream, text: &str) -> io::Result<()> {
+        match stream {
+            Stream::Stdout => self.stdout.write_all(text.as_bytes()),
+            Stream::Stderr => self.stderr.write_all(text.as_bytes()),
+        }
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.stdout.flush()?;
+        self.stderr.flush()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+    fn name(self) -> &'static str {
+        match self {
+            Stream::Stdout => "stdout",
+            Stream::Stderr => "stderr",
+        }
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Output>> = const { RefCell::new(None) };
+}
+
+/*
+Puts back the previously installed output, also when `f` panics.
+ */
+struct Restore(Option<Output>);
+
+impl Drop for Restore {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/*
+Runs `f` with everything it prints going to `output`.
+ */
+pub fn redirect<F, R>(output: Output, f: F) -> R
+    where F: FnOnce() -> R {
+    let previous = CURRENT.with(|current| current.borrow_mut().replace(output));
+    let _restore = Restore(previous);
+    f()
+}
+
+/*
+Everything printed while capturing, with stdout and stderr kept in the
+order they were written so the output can be replayed faithfully.
+ */
+#[derive(Clone, Default)]
+pub struct Captured {
+    chunks: Vec<(Stream, String)>,
+}
+
+impl Captured {
+    pub fn stdout(&self) -> String {
+        self.collect(Stream::Stdout)
+    }
+
+    pub fn stderr(&self) -> String {
+        self.collect(Stream::Stderr)
+    }
+
+    pub fn replay(&self, output: &mut Output) -> io::Result<()> {
+        for (stream, text) in &self.chunks {
+            output.write(*stream, text)?;
+        }
+        Ok(())
+    }
+
+    fn collect(&self, wanted: Stream) -> String {
+        self.chunks.iter()
+            .filter(|(stream, _)| *stream == wanted)
+            .map(|(_, text)| text.as_str())
+            .collect()
+    }
+}
+
+struct Recorder {
+    stream: Stream,
+    captured: Rc<RefCell<Captured>>,
+}
+
+impl Write for Recorder {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let text = String::from_utf8_lossy(buf);
+        let chunks = &mut self.captured.borrow_mut().chunks;
+        match chunks.last_mut() {
+            Some((stream, last)) if *stream == self.stream => last.push_str(&text),
+            _ => chunks.push((self.stream, text.into_owned())),
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub fn capture<F, R>(f: F) -> (R, Captured)
+    where F: FnOnce() -> R {
+    let captured = Rc::new(RefCell::new(Captured::default()));
+    let output = Output::new(
+        Recorder { stream: Stream::Stdout, captured: Rc::clone(&captured) },
+        Recorder { stream: Stream::Stderr, captured: Rc::clone(&captured) },
+    );
+    let result = redirect(output, f);
+    (result, captured.take())
+}
+
+pub fn print(stream: Stream, args: fmt::Arguments) {
+    /*
+    Formatting first keeps `CURRENT` free while `Display` impls run.
+     */
+    let text = fmt::format(args);
+    let written = CURRENT.with(|current| match current.borrow_mut().as_mut() {
+        Some(output) => output.write(stream, &text),
+        None => match stream {
+            Stream::Stdout => io::stdout().write_all(text.as_bytes()),
+            Stream::Stderr => io::stderr().write_all(text.as_bytes()),
+        },
+    });
+
+    if let Err(err) = written {
+        panic!("failed printing to {}: {}", stream.name(), err);
+    }
+}
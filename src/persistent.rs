@@ -0,0 +1,172 @@
+/*
+Immutable lists whose versions share their tails, for keeping many versions
+of something cheaply, like the states of an undo history. Where
+`c_custom_types::LinkedList::prepend` consumes the list, `prepend` here
+borrows it and returns a new list pointing at the same nodes:
+
+    let base = List::new().prepend(1);
+    let two = base.prepend(2);      // 2, 1, Nil
+    let three = base.prepend(3);    // 3, 1, Nil, sharing the node of `1`
+
+`head`, `tail`, `prepend` and `clone` take constant time. The nodes are
+reference counted, with `Rc` in `rc::List` and with `Arc` in `sync::List`,
+which can be sent to and shared between threads, as `std::rc` and
+`std::sync` name them.
+ */
+macro_rules! persistent_list {
+    ($pointer:ident) => {
+        use std::fmt;
+
+        pub struct List<T> {
+            head: Option<$pointer<Node<T>>>,
+        }
+
+        struct Node<T> {
+            elem: T,
+            next: Option<$pointer<Node<T>>>,
+        }
+
+        impl<T> List<T> {
+            pub fn new() -> List<T> {
+                List { head: None }
+            }
+
+            pub fn prepend(&self, elem: T) -> List<T> {
+                List { head: Some($pointer::new(Node { elem, next: self.head.clone() })) }
+            }
+
+            pub fn head(&self) -> Option<&T> {
+                self.head.as_ref().map(|node| &node.elem)
+            }
+
+            /*
+            The list after the head, which is empty for an empty list.
+             */
+            pub fn tail(&self) -> List<T> {
+                List { head: self.head.as_ref().and_then(|node| node.next.clone()) }
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.head.is_none()
+            }
+
+            pub fn len(&self) -> usize {
+                self.iter().count()
+            }
+
+            pub fn iter(&self) -> Iter<'_, T> {
+                Iter { next: self.head.as_deref() }
+            }
+
+            /*
+            Whether both lists start at the same node, and so share all of it.
+             */
+            pub fn ptr_eq(&self, other: &List<T>) -> bool {
+                match (&self.head, &other.head) {
+                    (Some(head), Some(other)) => $pointer::ptr_eq(head, other),
+                    (None, None) => true,
+                    _ => false,
+                }
+            }
+        }
+
+        impl<T> Clone for List<T> {
+            fn clone(&self) -> List<T> {
+                List { head: self.head.clone() }
+            }
+        }
+
+        impl<T> Default for List<T> {
+            fn default() -> List<T> {
+                List::new()
+            }
+        }
+
+        /*
+        Frees the nodes no other list holds one after the other, as dropping
+        them recursively would overflow the stack for long lists, and stops
+        at the first node that is still shared. `into_inner` rather than
+        `try_unwrap` hands the node to exactly one of the lists dropping
+        their last handles to it at the same time, where `try_unwrap` could
+        fail for both and leave the last of them to free the rest of the
+        nodes recursively.
+         */
+        impl<T> Drop for List<T> {
+            fn drop(&mut self) {
+                let mut next = self.head.take();
+                while let Some(node) = next {
+                    next = $pointer::into_inner(node).and_then(|mut node| node.next.take());
+                }
+            }
+        }
+
+        pub struct Iter<'a, T> {
+            next: Option<&'a Node<T>>,
+        }
+
+        impl<'a, T> Iterator for Iter<'a, T> {
+            type Item = &'a T;
+
+            fn next(&mut self) -> Option<&'a T> {
+                let node = self.next?;
+                self.next = node.next.as_deref();
+                Some(&node.elem)
+            }
+        }
+
+        impl<'a, T> IntoIterator for &'a List<T> {
+            type Item = &'a T;
+            type IntoIter = Iter<'a, T>;
+
+            fn into_iter(self) -> Iter<'a, T> {
+                self.iter()
+            }
+        }
+
+        /*
+        Keeps the order of the iterator, like `LinkedList`.
+         */
+        impl<T> FromIterator<T> for List<T> {
+            fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> List<T> {
+                let elems: Vec<T> = iter.into_iter().collect();
+                elems.into_iter().rev().fold(List::new(), |list, elem| list.prepend(elem))
+            }
+        }
+
+        impl<T: PartialEq> PartialEq for List<T> {
+            fn eq(&self, other: &List<T>) -> bool {
+                self.iter().eq(other.iter())
+            }
+        }
+
+        impl<T: fmt::Debug> fmt::Debug for List<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list().entries(self.iter()).finish()
+            }
+        }
+
+        /*
+        Renders like `LinkedList`, as `3, 2, 1, Nil`.
+         */
+        impl<T: fmt::Display> fmt::Display for List<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                for elem in self {
+                    write!(f, "{}, ", elem)?;
+                }
+                write!(f, "Nil")
+            }
+        }
+    };
+}
+
+pub mod rc {
+    use std::rc::Rc;
+
+    persistent_list!(Rc);
+}
+
+pub mod sync {
+    use std::sync::Arc;
+
+    persistent_list!(Arc);
+}
@@ -0,0 +1,557 @@
+use std::fmt::{self, Debug};
+
+use crate::table::display_width;
+
+/*
+A pretty-printer for any `Debug` value. The `{:?}` output is parsed back into
+a tree of structs, tuples, lists, sets, maps and atoms, which is then laid out
+again: in the expanded mode every non-empty collection is broken over lines
+as `{:#?}` does, and in the compact mode a value stays on one line as long as
+it fits in the width and only the values that do not fit are broken. Values
+deeper than the depth limit are elided as `Person { .. }`, `[..]` or `(..)`,
+and atoms and names can be colored with ANSI escapes for a terminal.
+
+Debug output that does not parse, like a hand-written `Debug` impl printing
+free text or a value nested deeper than `MAX_DEPTH`, is printed as `{:?}` or
+`{:#?}` would.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub enum Node {
+    /*
+    Numbers, strings and chars with their quotes, unit structs and variants,
+    and anything else that is not a collection.
+     */
+    Atom(String),
+    Struct { name: String, fields: Vec<(String, Node)>, non_exhaustive: bool },
+    Tuple { name: Option<String>, items: Vec<Node> },
+    List(Vec<Node>),
+    Set(Vec<Node>),
+    Map(Vec<(Node, Node)>),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Error {
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Compact,
+    Expanded,
+}
+
+/*
+How deep collections may nest in parsed Debug output. The parser and the
+layout recurse once per level, so a deeper value is an error rather than a
+stack overflow. Levels past the depth limit of a `Pretty` are skipped
+without being parsed and do not count.
+ */
+pub const MAX_DEPTH: usize = 128;
+
+const INDENT: &str = "    ";
+const RESET: &str = "\x1b[0m";
+const NAME: &str = "\x1b[36m";
+const STRING: &str = "\x1b[32m";
+const NUMBER: &str = "\x1b[33m";
+
+pub struct Pretty {
+    width: usize,
+    depth: Option<usize>,
+    mode: Mode,
+    color: bool,
+}
+
+impl Default for Pretty {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pretty {
+    pub fn new() -> Pretty {
+        Pretty { width: 80, depth: None, mode: Mode::Compact, color: false }
+    }
+
+    /*
+    The width in terminal columns the compact mode tries to stay within.
+    Atoms longer than that are never broken.
+     */
+    pub fn width(mut self, width: usize) -> Pretty {
+        self.width = width;
+        self
+    }
+
+    /*
+    How many levels of collections are shown; `depth(0)` elides the contents
+    of the value itself.
+     */
+    pub fn depth(mut self, depth: usize) -> Pretty {
+        self.depth = Some(depth);
+        self
+    }
+
+    pub fn mode(mut self, mode: Mode) -> Pretty {
+        self.mode = mode;
+        self
+    }
+
+    pub fn color(mut self, color: bool) -> Pretty {
+        self.color = color;
+        self
+    }
+
+    pub fn render<T: Debug + ?Sized>(&self, value: &T) -> String {
+        let debug = format!("{:?}", value);
+        match parse_to(&debug, self.depth) {
+            Ok(node) => self.render_node(&node),
+            Err(_) if self.mode == Mode::Expanded => format!("{:#?}", value),
+            Err(_) => debug,
+        }
+    }
+
+    pub fn render_node(&self, node: &Node) -> String {
+        let mut out = String::new();
+        self.layout(node, 0, 0, 0, &mut out);
+        out
+    }
+
+    /*
+    Writes `node` at the current position of `out`, where `used` columns of
+    the line are already taken by the indentation and a field name or a map
+    key, and the value is followed by a comma unless it is the outermost.
+     */
+    fn layout(&self, node: &Node, indent: usize, used: usize, depth: usize, out: &mut String) {
+        let elided = self.depth.is_some_and(|limit| depth >= limit);
+        let trailing = if depth == 0 { 0 } else { 1 };
+        let fits = || used + display_width(&self.flat(node, depth, false)) + trailing <= self.width;
+
+        if elided || node.is_empty() || self.mode == Mode::Compact && fits() {
+            out.push_str(&self.flat(node, depth, self.color));
+            return;
+        }
+
+        let inner = indent + 1;
+        let item = |out: &mut String, node: &Node, prefix: String, prefix_width: usize| {
+            out.push_str(&INDENT.repeat(inner));
+            out.push_str(&prefix);
+            self.layout(node, inner, inner * INDENT.len() + prefix_width, depth + 1, out);
+            out.push_str(",\n");
+        };
+        let (open, close) = node.brackets();
+        out.push_str(&self.name(node));
+        out.push_str(open.trim_end());
+        out.push('\n');
+        match node {
+            Node::Atom(_) => unreachable!("atoms are always flat"),
+            Node::Struct { fields, non_exhaustive, .. } => {
+                for (name, value) in fields {
+                    item(out, value, format!("{}: ", name), display_width(name) + 2);
+                }
+                if *non_exhaustive {
+                    out.push_str(&format!("{}..\n", INDENT.repeat(inner)));
+                }
+            }
+            Node::Tuple { items, .. } | Node::List(items) | Node::Set(items) => {
+                for value in items {
+                    item(out, value, String::new(), 0);
+                }
+            }
+            Node::Map(entries) => {
+                for (key, value) in entries {
+                    let flat_key = self.flat(key, depth + 1, false);
+                    item(out, value, format!("{}: ", self.flat(key, depth + 1, self.color)), display_width(&flat_key) + 2);
+                }
+            }
+        }
+        out.push_str(&INDENT.repeat(indent));
+        out.push_str(close.trim_start());
+    }
+
+    /*
+    The node on one line, which is exactly `{:?}` when nothing is elided.
+     */
+    fn flat(&self, node: &Node, depth: usize, color: bool) -> String {
+        if let Node::Atom(atom) = node {
+            return paint(atom, atom_color(atom), color);
+        }
+        let name = if color { self.name(node) } else { node.name().map_or(String::new(), str::to_owned) };
+        let (open, close) = node.brackets();
+        if node.is_empty() {
+            return format!("{}{}{}", name, open.trim_end(), close.trim_start());
+        }
+        if self.depth.is_some_and(|limit| depth >= limit) {
+            return format!("{}{}..{}", name, open, close);
+        }
+
+        let child = |node: &Node| self.flat(node, depth + 1, color);
+        let mut parts: Vec<String> = match node {
+            Node::Atom(_) => unreachable!("atoms returned above"),
+            Node::Struct { fields, .. } => fields.iter().map(|(name, value)| format!("{}: {}", name, child(value))).collect(),
+            Node::Tuple { items, .. } | Node::List(items) | Node::Set(items) => items.iter().map(child).collect(),
+            Node::Map(entries) => entries.iter().map(|(key, value)| format!("{}: {}", child(key), child(value))).collect(),
+        };
+        if let Node::Struct { non_exhaustive: true, .. } = node {
+            parts.push("..".to_owned());
+        }
+        let single = matches!(node, Node::Tuple { name: None, items } if items.len() == 1);
+        format!("{}{}{}{}{}", name, open, parts.join(", "), if single { "," } else { "" }, close)
+    }
+
+    fn name(&self, node: &Node) -> String {
+        node.name().map_or(String::new(), |name| paint(name, Some(NAME), self.color))
+    }
+}
+
+impl Node {
+    fn name(&self) -> Option<&str> {
+        match self {
+            Node::Struct { name, .. } => Some(name),
+            Node::Tuple { name, .. } => name.as_deref(),
+            _ => None,
+        }
+    }
+
+    fn brackets(&self) -> (&'static str, &'static str) {
+        match self {
+            Node::Atom(_) => ("", ""),
+            Node::Struct { .. } => (" { ", " }"),
+            Node::Tuple { .. } => ("(", ")"),
+            Node::List(_) => ("[", "]"),
+            Node::Set(_) | Node::Map(_) => ("{", "}"),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        match self {
+            Node::Atom(_) => true,
+            Node::Struct { fields, non_exhaustive, .. } => fields.is_empty() && !non_exhaustive,
+            Node::Tuple { items, .. } | Node::List(items) | Node::Set(items) => items.is_empty(),
+            Node::Map(entries) => entries.is_empty(),
+        }
+    }
+}
+
+fn atom_color(atom: &str) -> Option<&'static str> {
+    let first = atom.chars().next()?;
+    if first == '"' || first == '\'' {
+        Some(STRING)
+    } else if first.is_ascii_digit() || first == '-' || matches!(atom, "true" | "false" | "NaN" | "inf") {
+        Some(NUMBER)
+    } else if first.is_uppercase() {
+        Some(NAME)
+    } else {
+        None
+    }
+}
+
+fn paint(text: &str, color: Option<&str>, enabled: bool) -> String {
+    match color {
+        Some(color) if enabled => format!("{}{}{}", color, text, RESET),
+        _ => text.to_owned(),
+    }
+}
+
+/*
+Parses the output of `{:?}`, or of `{:#?}` since whitespace between the
+parts is ignored.
+ */
+pub fn parse(text: &str) -> Result<Node, Error> {
+    parse_to(text, None)
+}
+
+/*
+Parses collections down to `limit` levels; the ones below are skipped and
+left with a single `..` item, or as a non-exhaustive struct with no fields,
+which lays out the same once elided.
+ */
+fn parse_to(text: &str, limit: Option<usize>) -> Result<Node, Error> {
+    let mut parser = Parser { chars: text.chars().collect(), position: 0, depth: 0, limit };
+    let node = parser.value()?;
+    parser.skip_whitespace();
+    if parser.position < parser.chars.len() {
+        return Err(parser.error(format!("unexpected `{}` after the value", parser.chars[parser.position])));
+    }
+    Ok(node)
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    depth: usize,
+    limit: Option<usize>,
+}
+
+impl Parser {
+    fn error(&self, message: String) -> Error {
+        Error { column: self.position + 1, message }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(c) => Err(self.error(format!("expected `{}`, found `{}`", expected, c))),
+            None => Err(self.error(format!("expected `{}`, found the end", expected))),
+        }
+    }
+
+    fn value(&mut self) -> Result<Node, Error> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('[') => {
+                self.position += 1;
+                Ok(Node::List(self.nested(']', |parser| parser.items(']'), elided)?))
+            }
+            Some('(') => {
+                self.position += 1;
+                Ok(Node::Tuple { name: None, items: self.nested(')', |parser| parser.items(')'), elided)? })
+            }
+            Some('{') => {
+                self.position += 1;
+                self.nested('}', Parser::set_or_map, |empty| Node::Set(elided(empty)))
+            }
+            Some(quote @ ('"' | '\'')) => self.quoted(quote),
+            Some(_) => self.named(),
+            None => Err(self.error("expected a value, found the end".to_owned())),
+        }
+    }
+
+    fn quoted(&mut self, quote: char) -> Result<Node, Error> {
+        let start = self.position;
+        self.position += 1;
+        while let Some(c) = self.peek() {
+            self.position += 1;
+            match c {
+                '\\' => self.position += 1,
+                c if c == quote => return Ok(Node::Atom(self.chars[start..self.position].iter().collect())),
+                _ => {}
+            }
+        }
+        self.position = start;
+        Err(self.error(format!("unterminated `{}`", quote)))
+    }
+
+    /*
+    An atom, which is the name of a struct when `{` follows and of a tuple
+    struct when `(` follows right after it.
+     */
+    fn named(&mut self) -> Result<Node, Error> {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            let path = c == ':' && self.chars.get(self.position + 1) == Some(&':');
+            if path {
+                self.position += 2;
+            } else if c.is_whitespace() || ",:{}()[]".contains(c) {
+                break;
+            } else {
+                self.position += 1;
+            }
+        }
+        if start == self.position {
+            return Err(self.error(format!("expected a value, found `{}`", self.chars[start])));
+        }
+        let name: String = self.chars[start..self.position].iter().collect();
+
+        if self.peek() == Some('(') {
+            self.position += 1;
+            return Ok(Node::Tuple { name: Some(name), items: self.nested(')', |parser| parser.items(')'), elided)? });
+        }
+        let after_name = self.position;
+        self.skip_whitespace();
+        if self.peek() == Some('{') {
+            self.position += 1;
+            let fields = |empty: bool| Node::Struct { name: name.clone(), fields: Vec::new(), non_exhaustive: !empty };
+            return self.nested('}', |parser| parser.fields(name.clone()), fields);
+        }
+        self.position = after_name;
+        Ok(Node::Atom(name))
+    }
+
+    /*
+    Parses the inside of a collection whose opening bracket was just
+    consumed, up to and including `close`. Past the depth limit the inside
+    is skipped instead and `elided` makes the node from whether it was
+    empty.
+     */
+    fn nested<T>(
+        &mut self,
+        close: char,
+        parse: impl FnOnce(&mut Parser) -> Result<T, Error>,
+        elided: impl FnOnce(bool) -> T,
+    ) -> Result<T, Error> {
+        if self.limit.is_some_and(|limit| self.depth >= limit) {
+            return self.skip(close).map(elided);
+        }
+        if self.depth == MAX_DEPTH {
+            return Err(Error { column: self.position, message: "nesting too deep".to_owned() });
+        }
+        self.depth += 1;
+        let nested = parse(self);
+        self.depth -= 1;
+        nested
+    }
+
+    /*
+    Skips to after the `close` matching the bracket before, in a loop so
+    that it goes as deep as the text does, and returns whether there was
+    nothing in between.
+     */
+    fn skip(&mut self, close: char) -> Result<bool, Error> {
+        self.skip_whitespace();
+        let empty = self.peek() == Some(close);
+        let mut closes = vec![close];
+        while let Some(&close) = closes.last() {
+            match self.peek() {
+                Some(quote @ ('"' | '\'')) => {
+                    self.quoted(quote)?;
+                    continue;
+                }
+                Some('[') => closes.push(']'),
+                Some('(') => closes.push(')'),
+                Some('{') => closes.push('}'),
+                Some(c @ (']' | ')' | '}')) if c != close => {
+                    return Err(self.error(format!("expected `{}`, found `{}`", close, c)));
+                }
+                Some(']' | ')' | '}') => {
+                    closes.pop();
+                }
+                Some(_) => {}
+                None => return Err(self.error(format!("expected `{}`, found the end", close))),
+            }
+            self.position += 1;
+        }
+        Ok(empty)
+    }
+
+    fn items(&mut self, close: char) -> Result<Vec<Node>, Error> {
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some(close) {
+                self.position += 1;
+                return Ok(items);
+            }
+            items.push(self.value()?);
+            if !self.separator(close)? {
+                return Ok(items);
+            }
+        }
+    }
+
+    /*
+    After an item: consumes a comma and returns whether more items may
+    follow, or consumes the closing bracket and returns `false`.
+     */
+    fn separator(&mut self, close: char) -> Result<bool, Error> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(',') => {
+                self.position += 1;
+                Ok(true)
+            }
+            Some(c) if c == close => {
+                self.position += 1;
+                Ok(false)
+            }
+            Some(c) => Err(self.error(format!("expected `,` or `{}`, found `{}`", close, c))),
+            None => Err(self.error(format!("expected `,` or `{}`, found the end", close))),
+        }
+    }
+
+    fn set_or_map(&mut self) -> Result<Node, Error> {
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(Node::Set(Vec::new()));
+        }
+        let first = self.value()?;
+        self.skip_whitespace();
+        if self.peek() != Some(':') {
+            let mut items = vec![first];
+            if self.separator('}')? {
+                items.extend(self.items('}')?);
+            }
+            return Ok(Node::Set(items));
+        }
+
+        let mut entries = Vec::new();
+        let mut key = first;
+        loop {
+            self.expect(':')?;
+            entries.push((key, self.value()?));
+            if !self.separator('}')? {
+                return Ok(Node::Map(entries));
+            }
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.position += 1;
+                return Ok(Node::Map(entries));
+            }
+            key = self.value()?;
+        }
+    }
+
+    fn fields(&mut self, name: String) -> Result<Node, Error> {
+        let mut fields = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.peek() == Some('}') {
+                self.position += 1;
+                return Ok(Node::Struct { name, fields, non_exhaustive: false });
+            }
+            if self.chars[self.position..].starts_with(&['.', '.']) {
+                self.position += 2;
+                self.skip_whitespace();
+                if self.peek() == Some(',') {
+                    self.position += 1;
+                }
+                self.expect('}')?;
+                return Ok(Node::Struct { name, fields, non_exhaustive: true });
+            }
+
+            let start = self.position;
+            while self.peek().is_some_and(|c| c.is_alphanumeric() || c == '_' || c == '#') {
+                self.position += 1;
+            }
+            if start == self.position {
+                return Err(self.error("expected a field name".to_owned()));
+            }
+            let field: String = self.chars[start..self.position].iter().collect();
+            self.expect(':')?;
+            fields.push((field, self.value()?));
+            if !self.separator('}')? {
+                return Ok(Node::Struct { name, fields, non_exhaustive: false });
+            }
+        }
+    }
+}
+
+fn elided(empty: bool) -> Vec<Node> {
+    if empty {
+        Vec::new()
+    } else {
+        vec![Node::Atom("..".to_owned())]
+    }
+}
@@ -0,0 +1,106 @@
+use crate::chapter::{Chapter, Section};
+use crate::*;
+
+macro_rules! chapter {
+    ($module:ident, $title:expr) => {
+        Chapter::new(
+            stringify!($module),
+            $title,
+            $module::SECTIONS,
+            include_str!(concat!(stringify!($module), ".rs")),
+        )
+    };
+}
+
+pub const CHAPTERS: &[Chapter] = &[
+    chapter!(a_hello_world, "Hello World"),
+    chapter!(b_primitives, "Primitives"),
+    chapter!(c_custom_types, "Custom Types"),
+    chapter!(d_variable_bindings, "Variable Bindings"),
+    chapter!(e_rust, "Types"),
+    chapter!(f_conversion, "Conversion"),
+    chapter!(g_expressions, "Expressions"),
+    chapter!(h_flow_of_control, "Flow of Control"),
+    chapter!(i_functions, "Functions"),
+    chapter!(j_modules, "Modules"),
+    chapter!(k_crates, "Crates"),
+    chapter!(l_cargo, "Cargo"),
+    chapter!(m_attributes, "Attributes"),
+    chapter!(n_generics, "Generics"),
+    chapter!(o_scoping_rules, "Scoping rules"),
+];
+
+/*
+Looks a chapter up by its name, e.g. `flow_of_control`, or by its module,
+e.g. `h_flow_of_control`.
+ */
+pub fn find(name: &str) -> Option<&'static Chapter> {
+    CHAPTERS.iter().find(|chapter| chapter.name() == name || chapter.module == name)
+}
+
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub chapter: &'static Chapter,
+    pub section: &'static Section,
+}
+
+impl Entry {
+    pub fn path(&self) -> String {
+        format!("{}::{}", self.chapter.name(), self.section.name)
+    }
+}
+
+/*
+Selects sections by `<chapter>` or `<chapter>::<section>`, where either part
+may be a glob using `*` and `?`, e.g. `scoping_rules::lifetime_*`.
+ */
+pub fn select(pattern: &str) -> Vec<Entry> {
+    let (chapter_pattern, section_pattern) = match pattern.split_once("::") {
+        Some((chapter, section)) => (chapter, section),
+        None => (pattern, "*"),
+    };
+
+    let mut entries = Vec::new();
+    for chapter in CHAPTERS {
+        if !glob_match(chapter_pattern, chapter.name()) && !glob_match(chapter_pattern, chapter.module) {
+            continue;
+        }
+        for section in chapter.sections {
+            if glob_match(section_pattern, section.name) {
+                entries.push(Entry { chapter, section });
+            }
+        }
+    }
+    entries
+}
+
+pub fn all() -> Vec<Entry> {
+    select("*")
+}
+
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    /*
+    Greedy matching with backtracking to the most recent `*`.
+     */
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
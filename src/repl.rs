@@ -0,0 +1,228 @@
+use std::io::{self, BufRead};
+
+use crate::golden::{self, Verdict};
+use crate::output::{Output, Stream};
+use crate::registry::{self, Entry, CHAPTERS};
+use crate::runner::{self, Outcome};
+use crate::source;
+
+const HELP: &str = "\
+commands:
+    chapters               list the chapters
+    sections [<chapter>]   list the sections of the current or given chapter
+    go <selector>          move to a chapter or a section, like `go generics`
+                           or `go flow_of_control::match_guards`
+    next, prev             move to the next or the previous section
+    show                   print the source of the current section
+    run [<selector>]       run the current section, or move to another and run it
+    rerun                  run the last section that ran again
+    diff                   compare the output of the current section with the
+                           output annotated in its comments
+    help                   show this message
+    quit                   leave, like the end of the input";
+
+/*
+An interactive session that walks the sections of every chapter in book
+order. It keeps the position of the current section and the outcome of the
+last run, so that `rerun` and `diff` do not need a selector.
+ */
+pub struct Repl {
+    entries: Vec<Entry>,
+    current: usize,
+    last: Option<(usize, Outcome)>,
+}
+
+impl Default for Repl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Repl {
+    pub fn new() -> Repl {
+        Repl { entries: registry::all(), current: 0, last: None }
+    }
+
+    pub fn current(&self) -> Entry {
+        self.entries[self.current]
+    }
+
+    pub fn prompt(&self) -> String {
+        format!("{}> ", self.current().path())
+    }
+
+    /*
+    Returns whether the session goes on, that is `false` after `quit`.
+     */
+    pub fn eval(&mut self, line: &str, output: &mut Output) -> io::Result<bool> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let reply = match words.as_slice() {
+            [] => return Ok(true),
+            ["quit" | "exit" | "q"] => return Ok(false),
+            ["help" | "?"] => Ok(format!("{}\n", HELP)),
+            ["chapters"] => Ok(self.chapters()),
+            ["sections" | "ls"] => self.sections(None),
+            ["sections" | "ls", chapter] => self.sections(Some(chapter)),
+            ["go", selector] => self.go(selector),
+            ["next" | "n"] => self.step(1),
+            ["prev" | "p"] => self.step(-1),
+            ["show"] => self.show(),
+            ["run" | "r"] => return self.run(self.current, output).map(|()| true),
+            ["run" | "r", selector] => match self.go(selector) {
+                Ok(_) => return self.run(self.current, output).map(|()| true),
+                Err(message) => Err(message),
+            },
+            ["rerun"] => match &self.last {
+                Some((index, _)) => return self.run(*index, output).map(|()| true),
+                None => Err("nothing has run yet, see `run`".to_owned()),
+            },
+            ["diff" | "d"] => Ok(self.diff()),
+            _ => Err(format!("unknown command `{}`, see `help`", line.trim())),
+        };
+
+        match reply {
+            Ok(text) => output.write(Stream::Stdout, &text)?,
+            Err(message) => output.write(Stream::Stderr, &format!("error: {}\n", message))?,
+        }
+        Ok(true)
+    }
+
+    fn chapters(&self) -> String {
+        let current = self.current().chapter.module;
+        CHAPTERS.iter()
+            .map(|chapter| {
+                let mark = if chapter.module == current { '*' } else { ' ' };
+                format!("{} {:<20} {}\n", mark, chapter.name(), chapter.title)
+            })
+            .collect()
+    }
+
+    fn sections(&self, chapter: Option<&str>) -> Result<String, String> {
+        let chapter = match chapter {
+            Some(name) => registry::find(name).ok_or_else(|| format!("no chapter `{}`, see `chapters`", name))?,
+            None => self.current().chapter,
+        };
+        let current = self.current();
+        let width = chapter.sections.iter().map(|section| section.name.len()).max().unwrap_or(0);
+
+        let mut text = format!("{}\n", chapter.title);
+        for section in chapter.sections {
+            let mark = if std::ptr::eq(section, current.section) { '*' } else { ' ' };
+            let note = if section.panics { " (panics)" } else { "" };
+            text.push_str(&format!("{} {:<width$} {}{}\n", mark, section.name, section.title, note, width = width));
+        }
+        Ok(text)
+    }
+
+    fn go(&mut self, selector: &str) -> Result<String, String> {
+        let selected = registry::select(selector);
+        let first = selected.first().ok_or_else(|| format!("nothing matches `{}`, see `chapters`", selector))?;
+        self.current = self.entries.iter()
+            .position(|entry| std::ptr::eq(entry.section, first.section))
+            .expect("every selected section is registered");
+        Ok(self.position())
+    }
+
+    fn step(&mut self, delta: isize) -> Result<String, String> {
+        match self.current.checked_add_signed(delta) {
+            Some(index) if index < self.entries.len() => {
+                self.current = index;
+                Ok(self.position())
+            }
+            _ if delta < 0 => Err("already at the first section".to_owned()),
+            _ => Err("already at the last section".to_owned()),
+        }
+    }
+
+    fn position(&self) -> String {
+        let entry = self.current();
+        let index = entry.chapter.sections.iter()
+            .position(|section| std::ptr::eq(section, entry.section))
+            .unwrap_or(0);
+        format!(
+            "{} ({}, section {} of {})\n",
+            entry.section.title,
+            entry.chapter.title,
+            index + 1,
+            entry.chapter.sections.len()
+        )
+    }
+
+    fn show(&self) -> Result<String, String> {
+        let entry = self.current();
+        let lines = source::function(entry.chapter.source, entry.section.name)
+            .ok_or_else(|| format!("no source for `{}`", entry.path()))?;
+        Ok(lines.iter().map(|line| format!("{}\n", line)).collect())
+    }
+
+    fn run(&mut self, index: usize, output: &mut Output) -> io::Result<()> {
+        let entry = self.entries[index];
+        let outcome = runner::run_isolated(&entry);
+        outcome.captured.replay(output)?;
+        if let Some(panic) = &outcome.panic {
+            output.write(Stream::Stderr, &format!("section `{}` panicked {}\n", entry.path(), panic))?;
+        }
+        self.last = Some((index, outcome));
+        Ok(())
+    }
+
+    /*
+    Compares against the last run when it was the current section, so that
+    the diff shows the output the user just saw, and runs it quietly otherwise.
+     */
+    fn diff(&mut self) -> String {
+        let entry = self.current();
+        let outcome = match self.last.take() {
+            Some((index, outcome)) if index == self.current => outcome,
+            _ => runner::run_isolated(&entry),
+        };
+        let text = describe_diff(&entry, &outcome);
+        self.last = Some((self.current, outcome));
+        text
+    }
+}
+
+fn describe_diff(entry: &Entry, outcome: &Outcome) -> String {
+    let expected = golden::expected_output(entry.chapter.source, entry.section.name);
+    let missing = match golden::verify(entry, outcome) {
+        Verdict::Pass => Vec::new(),
+        Verdict::Mismatch { missing } => missing,
+        Verdict::Panicked(panic) => return format!("the section panicked {}\n", panic),
+        Verdict::Unannotated => return format!("`{}` has no annotated output\n", entry.path()),
+        Verdict::Skipped(reason) => return format!("`{}` is not compared: {}\n", entry.path(), reason),
+    };
+
+    let mut text = String::from("expected output, in order:\n");
+    for (index, line) in expected.iter().enumerate() {
+        let mark = if missing.contains(&index) { "missing" } else { "ok" };
+        text.push_str(&format!("    {:<8}{}\n", mark, line));
+    }
+    text.push_str("actual output:\n");
+    for line in outcome.captured.stdout().lines() {
+        text.push_str(&format!("    | {}\n", line));
+    }
+    text.push_str(&format!("{} of {} expected lines found\n", expected.len() - missing.len(), expected.len()));
+    text
+}
+
+/*
+Reads commands line by line until `quit` or the end of the input.
+ */
+pub fn run<R: BufRead>(input: R, mut output: Output) -> io::Result<()> {
+    let mut repl = Repl::new();
+    output.write(Stream::Stdout, "Rust by Example, type `help` for the commands.\n")?;
+
+    let mut lines = input.lines();
+    loop {
+        output.write(Stream::Stdout, &repl.prompt())?;
+        output.flush()?;
+        match lines.next() {
+            Some(line) => {
+                if !repl.eval(&line?, &mut output)? {
+                    return Ok(());
+                }
+            }
+            None => return output.write(Stream::Stdout, "\n"),
+        }
+    }
+}
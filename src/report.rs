@@ -0,0 +1,241 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::chapter::Chapter;
+use crate::golden::{self, Verdict};
+use crate::json::Value;
+use crate::registry::Entry;
+use crate::runner::{self, Outcome};
+
+pub struct Report {
+    pub chapters: Vec<ChapterReport>,
+}
+
+pub struct ChapterReport {
+    pub chapter: &'static Chapter,
+    pub sections: Vec<SectionReport>,
+}
+
+pub struct SectionReport {
+    pub entry: Entry,
+    pub outcome: Outcome,
+    pub expected: Vec<String>,
+    pub verdict: Verdict,
+}
+
+#[derive(Default)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub unannotated: usize,
+    pub skipped: usize,
+    pub duration: Duration,
+}
+
+/*
+Runs every entry isolated and checks it against its annotated output,
+grouping the results by chapter in the order the entries were given.
+ */
+pub fn collect(entries: &[Entry]) -> Report {
+    let mut chapters: Vec<ChapterReport> = Vec::new();
+    for entry in entries {
+        let outcome = runner::run_isolated(entry);
+        let verdict = golden::verify(entry, &outcome);
+        let expected = golden::expected_output(entry.chapter.source, entry.section.name);
+        let section = SectionReport { entry: *entry, outcome, expected, verdict };
+
+        match chapters.last_mut() {
+            Some(last) if last.chapter.module == entry.chapter.module => last.sections.push(section),
+            _ => chapters.push(ChapterReport { chapter: entry.chapter, sections: vec![section] }),
+        }
+    }
+    Report { chapters }
+}
+
+impl Report {
+    pub fn sections(&self) -> impl Iterator<Item = &SectionReport> {
+        self.chapters.iter().flat_map(|chapter| chapter.sections.iter())
+    }
+
+    pub fn summary(&self) -> Summary {
+        Summary::of(self.sections())
+    }
+
+    pub fn to_json(&self) -> Value {
+        let summary = self.summary();
+        Value::object([
+            ("summary", summary.to_json()),
+            ("chapters", Value::Array(self.chapters.iter().map(ChapterReport::to_json).collect())),
+        ])
+    }
+
+    pub fn to_junit(&self) -> String {
+        let summary = self.summary();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"rust-by-example\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+            summary.total(), summary.failed, summary.skipped, summary.duration.as_secs_f64()
+        );
+        for chapter in &self.chapters {
+            chapter.write_junit(&mut xml);
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+}
+
+impl ChapterReport {
+    fn to_json(&self) -> Value {
+        Value::object([
+            ("name", self.chapter.name().into()),
+            ("module", self.chapter.module.into()),
+            ("title", self.chapter.title.into()),
+            ("summary", Summary::of(self.sections.iter()).to_json()),
+            ("sections", Value::Array(self.sections.iter().map(SectionReport::to_json).collect())),
+        ])
+    }
+
+    fn write_junit(&self, xml: &mut String) {
+        let summary = Summary::of(self.sections.iter());
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" time=\"{:.6}\">",
+            escape_xml(self.chapter.name()), summary.total(), summary.failed, summary.skipped,
+            summary.duration.as_secs_f64()
+        );
+        for section in &self.sections {
+            section.write_junit(xml);
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+}
+
+impl SectionReport {
+    pub fn status(&self) -> &'static str {
+        match self.verdict {
+            Verdict::Pass => "pass",
+            Verdict::Mismatch { .. } => "fail",
+            Verdict::Panicked(_) => "panic",
+            Verdict::Unannotated => "unannotated",
+            Verdict::Skipped(_) => "skipped",
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let missing = match &self.verdict {
+            Verdict::Mismatch { missing } => missing.iter().map(|&index| self.expected[index].clone()).collect(),
+            _ => Vec::new(),
+        };
+        let panic = self.outcome.panic.as_ref().map(|panic| Value::object([
+            ("message", panic.message.as_str().into()),
+            ("location", panic.location.clone().into()),
+        ]));
+
+        Value::object([
+            ("name", self.entry.section.name.into()),
+            ("path", self.entry.path().into()),
+            ("title", self.entry.section.title.into()),
+            ("status", self.status().into()),
+            ("duration_ms", (self.outcome.duration.as_secs_f64() * 1000.0).into()),
+            ("stdout", self.outcome.captured.stdout().into()),
+            ("stderr", self.outcome.captured.stderr().into()),
+            ("expected", self.expected.clone().into()),
+            ("missing", missing.into()),
+            ("panic", panic.unwrap_or(Value::Null)),
+        ])
+    }
+
+    fn write_junit(&self, xml: &mut String) {
+        let mut body = String::new();
+        match &self.verdict {
+            Verdict::Pass | Verdict::Unannotated => {}
+            Verdict::Mismatch { missing } => {
+                let missing: Vec<&str> = missing.iter().map(|&index| self.expected[index].as_str()).collect();
+                let _ = writeln!(
+                    body,
+                    "      <failure message=\"output does not match the annotated output\">missing, in order:\n{}</failure>",
+                    escape_xml(&missing.join("\n"))
+                );
+            }
+            Verdict::Panicked(panic) => {
+                let message = escape_xml(&format!("panicked {}", panic));
+                let _ = writeln!(body, "      <error message=\"{}\" type=\"panic\"/>", message);
+            }
+            Verdict::Skipped(reason) => {
+                let _ = writeln!(body, "      <skipped message=\"{}\"/>", escape_xml(reason));
+            }
+        }
+        let stdout = self.outcome.captured.stdout();
+        if !stdout.is_empty() {
+            let _ = writeln!(body, "      <system-out>{}</system-out>", escape_xml(&stdout));
+        }
+        let stderr = self.outcome.captured.stderr();
+        if !stderr.is_empty() {
+            let _ = writeln!(body, "      <system-err>{}</system-err>", escape_xml(&stderr));
+        }
+
+        let _ = write!(
+            xml,
+            "    <testcase classname=\"{}\" name=\"{}\" time=\"{:.6}\"",
+            escape_xml(self.entry.chapter.name()), escape_xml(self.entry.section.name),
+            self.outcome.duration.as_secs_f64()
+        );
+        if body.is_empty() {
+            xml.push_str("/>\n");
+        } else {
+            let _ = write!(xml, ">\n{}    </testcase>\n", body);
+        }
+    }
+}
+
+impl Summary {
+    fn of<'a, I>(sections: I) -> Summary
+        where I: Iterator<Item = &'a SectionReport> {
+        let mut summary = Summary::default();
+        for section in sections {
+            match section.verdict {
+                Verdict::Pass => summary.passed += 1,
+                Verdict::Mismatch { .. } | Verdict::Panicked(_) => summary.failed += 1,
+                Verdict::Unannotated => summary.unannotated += 1,
+                Verdict::Skipped(_) => summary.skipped += 1,
+            }
+            summary.duration += section.outcome.duration;
+        }
+        summary
+    }
+
+    pub fn total(&self) -> usize {
+        self.passed + self.failed + self.unannotated + self.skipped
+    }
+
+    fn to_json(&self) -> Value {
+        Value::object([
+            ("passed", self.passed.into()),
+            ("failed", self.failed.into()),
+            ("unannotated", self.unannotated.into()),
+            ("skipped", self.skipped.into()),
+            ("duration_ms", (self.duration.as_secs_f64() * 1000.0).into()),
+        ])
+    }
+}
+
+/*
+Besides escaping markup, drops the control characters XML 1.0 doesn't allow.
+ */
+pub fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,89 @@
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use crate::output::{self, Captured};
+use crate::registry::Entry;
+
+pub struct Outcome {
+    pub captured: Captured,
+    pub panic: Option<Panic>,
+    pub duration: Duration,
+}
+
+pub struct Panic {
+    pub message: String,
+    pub location: Option<String>,
+}
+
+impl fmt::Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(f, "'{}' at {}", self.message, location),
+            None => write!(f, "'{}'", self.message),
+        }
+    }
+}
+
+thread_local! {
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/*
+Runs a section with its output captured and a panic caught, so that a
+diverging example or a failing `assert_eq!` doesn't take the whole run down.
+ */
+pub fn run_isolated(entry: &Entry) -> Outcome {
+    install_panic_hook();
+    trace!("running {}", entry.path());
+
+    ISOLATED.with(|isolated| isolated.set(true));
+    let started = Instant::now();
+    let (result, captured) = output::capture(|| panic::catch_unwind(AssertUnwindSafe(entry.section.run)));
+    let duration = started.elapsed();
+    ISOLATED.with(|isolated| isolated.set(false));
+
+    let panic = result.err().map(|payload| Panic {
+        message: panic_message(payload.as_ref()),
+        location: LOCATION.with(|location| location.borrow_mut().take()),
+    });
+    match &panic {
+        Some(panic) => debug!("{} panicked {} after {:.1?}", entry.path(), panic, duration),
+        None => debug!("{} ran in {:.1?}", entry.path(), duration),
+    }
+    Outcome { captured, panic, duration }
+}
+
+/*
+The hook is process wide. While a section runs isolated on this thread it
+only records where the panic happened instead of printing the usual message,
+everything else goes to the hook that was installed before.
+ */
+fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if ISOLATED.with(Cell::get) {
+                let location = info.location().map(|location| location.to_string());
+                LOCATION.with(|current| *current.borrow_mut() = location);
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<dyn Any>".to_owned()
+    }
+}
@@ -0,0 +1,113 @@
+/*
+Helpers to look up the source of a chapter's sections. Every section is a
+top-level `fn <name>()` whose body ends at the first line starting with `}`.
+ */
+
+pub fn function<'a>(source: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let signature = format!("fn {}(", name);
+    let mut lines = source.lines().skip_while(|line| !line.starts_with(&signature));
+
+    let mut function = vec![lines.next()?];
+    for line in lines {
+        function.push(line);
+        if line.starts_with('}') {
+            return Some(function);
+        }
+    }
+    None
+}
+
+pub fn body<'a>(source: &'a str, name: &str) -> Option<Vec<&'a str>> {
+    let function = function(source, name)?;
+    Some(function[1..function.len() - 1].to_vec())
+}
+
+/*
+Returns the lines between the end of the previous section, or the `SECTIONS`
+table for the first one, and the signature of `name`. That is where a
+chapter defines the types and helpers the section uses.
+ */
+pub fn preamble<'a>(source: &'a str, name: &str, sections: &[&str]) -> Option<Vec<&'a str>> {
+    let lines: Vec<&str> = source.lines().collect();
+    let signature = |name: &str| {
+        let signature = format!("fn {}(", name);
+        lines.iter().position(|line| line.starts_with(&signature))
+    };
+
+    let start = signature(name)?;
+    let previous = sections.iter()
+        .filter_map(|section| signature(section))
+        .filter(|&index| index < start)
+        .max();
+    let end_of_previous = match previous {
+        Some(index) => lines[index..].iter().position(|line| line.starts_with('}')).map(|end| index + end + 1),
+        None => lines.iter().position(|line| *line == "];").map(|end| end + 1),
+    };
+    Some(lines[end_of_previous.unwrap_or(0)..start].to_vec())
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Line<'a> {
+    Blank,
+    Code(&'a str),
+    Comment(&'a str),
+    BlockComment(&'a str),
+}
+
+/*
+Classifies each line of a function body, keeping track of `/* ... */` blocks.
+The text of a line comment is kept without the leading `// ` so that
+indentation that belongs to the comment, e.g. `//   -1 as a u8 is : 255`,
+is preserved.
+ */
+pub fn classify<'a>(lines: &[&'a str]) -> Vec<Line<'a>> {
+    let mut in_block = false;
+    lines.iter().map(|line| {
+        let trimmed = line.trim();
+        if in_block || trimmed.starts_with("/*") {
+            in_block = !trimmed.ends_with("*/");
+            Line::BlockComment(trimmed)
+        } else if let Some(text) = trimmed.strip_prefix("//") {
+            Line::Comment(text.strip_prefix(' ').unwrap_or(text))
+        } else if trimmed.is_empty() {
+            Line::Blank
+        } else {
+            Line::Code(line)
+        }
+    }).collect()
+}
+
+/*
+Splits a line of code from its trailing `//` comment, whose text is kept as
+by `classify`. Slashes inside string and character literals are not a
+comment.
+ */
+pub fn split_comment(line: &str) -> (&str, Option<&str>) {
+    let mut in_string = false;
+    let mut chars = line.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            }
+            '"' => in_string = !in_string,
+            '\'' if !in_string => {
+                let rest = &line[index + 1..];
+                let literal = if rest.starts_with('\\') { rest[2..].find('\'').map(|end| end + 3) } else {
+                    rest.chars().next().filter(|_| rest.chars().nth(1) == Some('\'')).map(|c| c.len_utf8() + 1)
+                };
+                if let Some(length) = literal {
+                    while chars.peek().is_some_and(|&(next, _)| next <= index + length) {
+                        chars.next();
+                    }
+                }
+            }
+            '/' if !in_string && line[index + 1..].starts_with('/') => {
+                let text = &line[index + 2..];
+                return (&line[..index], Some(text.strip_prefix(' ').unwrap_or(text)));
+            }
+            _ => {}
+        }
+    }
+    (line, None)
+}
@@ -0,0 +1,218 @@
+use crate::format::Value;
+
+/*
+Renders rows of any type as a table with named columns. Each column turns a
+row into a `format::Value`; numbers are right-aligned and everything else
+left-aligned unless the column says otherwise, and a precision rounds the
+floats of a column. Widths are measured in terminal columns, so that `°` in
+`City` or wide CJK characters keep the columns straight.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    Left,
+    Center,
+    Right,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Style {
+    Plain,
+    Markdown,
+    Csv,
+}
+
+pub struct Column<'a, R> {
+    header: String,
+    align: Option<Align>,
+    precision: Option<usize>,
+    value: Box<dyn Fn(&R) -> Value + 'a>,
+}
+
+impl<'a, R> Column<'a, R> {
+    pub fn new<F, V>(header: &str, value: F) -> Column<'a, R>
+        where F: Fn(&R) -> V + 'a, V: Into<Value> {
+        Column {
+            header: header.to_owned(),
+            align: None,
+            precision: None,
+            value: Box::new(move |row| value(row).into()),
+        }
+    }
+
+    pub fn align(mut self, align: Align) -> Column<'a, R> {
+        self.align = Some(align);
+        self
+    }
+
+    pub fn precision(mut self, precision: usize) -> Column<'a, R> {
+        self.precision = Some(precision);
+        self
+    }
+
+    fn cell(&self, row: &R) -> (String, bool) {
+        match ((self.value)(row), self.precision) {
+            (Value::Float(float), Some(precision)) => (format!("{:.*}", precision, float), true),
+            (value, _) => {
+                let numeric = matches!(value, Value::Integer(_) | Value::Float(_));
+                (value.to_string(), numeric)
+            }
+        }
+    }
+}
+
+pub struct Table<'a, R> {
+    columns: Vec<Column<'a, R>>,
+    padding: usize,
+}
+
+impl<R> Default for Table<'_, R> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, R> Table<'a, R> {
+    pub fn new() -> Table<'a, R> {
+        Table { columns: Vec::new(), padding: 1 }
+    }
+
+    pub fn with(mut self, column: Column<'a, R>) -> Table<'a, R> {
+        self.columns.push(column);
+        self
+    }
+
+    /*
+    The number of spaces on each side of a cell, so that columns of plain
+    tables are `2 * padding` apart.
+     */
+    pub fn padding(mut self, padding: usize) -> Table<'a, R> {
+        self.padding = padding;
+        self
+    }
+
+    pub fn render<'r, I>(&self, rows: I, style: Style) -> String
+        where I: IntoIterator<Item = &'r R>, R: 'r {
+        let cells: Vec<Vec<(String, bool)>> = rows.into_iter()
+            .map(|row| self.columns.iter().map(|column| column.cell(row)).collect())
+            .collect();
+
+        /*
+        A column is right-aligned by default when all of its cells are numbers.
+         */
+        let aligns: Vec<Align> = self.columns.iter().enumerate()
+            .map(|(index, column)| {
+                let numeric = !cells.is_empty() && cells.iter().all(|row| row[index].1);
+                column.align.unwrap_or(if numeric { Align::Right } else { Align::Left })
+            })
+            .collect();
+        let header: Vec<String> = self.columns.iter().map(|column| column.header.clone()).collect();
+        let body: Vec<Vec<String>> = cells.into_iter()
+            .map(|row| row.into_iter().map(|(text, _)| text).collect())
+            .collect();
+
+        match style {
+            Style::Plain => self.plain(&header, &body, &aligns),
+            Style::Markdown => self.markdown(&header, &body, &aligns),
+            Style::Csv => csv(&header, &body),
+        }
+    }
+
+    fn plain(&self, header: &[String], body: &[Vec<String>], aligns: &[Align]) -> String {
+        let widths = widths(header, body);
+        let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+        let gap = " ".repeat(2 * self.padding);
+
+        let mut table = String::new();
+        for row in [header, &rule].into_iter().chain(body.iter().map(Vec::as_slice)) {
+            table.push_str(padded(row, &widths, aligns).join(&gap).trim_end());
+            table.push('\n');
+        }
+        table
+    }
+
+    fn markdown(&self, header: &[String], body: &[Vec<String>], aligns: &[Align]) -> String {
+        let escape = |row: &[String]| -> Vec<String> { row.iter().map(|cell| cell.replace('|', "\\|")).collect() };
+        let header = escape(header);
+        let body: Vec<Vec<String>> = body.iter().map(|row| escape(row)).collect();
+        let widths: Vec<usize> = widths(&header, &body).into_iter().map(|width| width.max(3)).collect();
+        let rule: Vec<String> = widths.iter().zip(aligns).map(|(&width, &align)| rule(width, align)).collect();
+
+        let space = " ".repeat(self.padding);
+        let separator = format!("{}|{}", space, space);
+        let mut table = String::new();
+        for row in [header.as_slice(), &rule].into_iter().chain(body.iter().map(Vec::as_slice)) {
+            table.push_str(&format!("|{}{}{}|\n", space, padded(row, &widths, aligns).join(&separator), space));
+        }
+        table
+    }
+}
+
+fn padded(row: &[String], widths: &[usize], aligns: &[Align]) -> Vec<String> {
+    row.iter().zip(widths).zip(aligns)
+        .map(|((cell, &width), &align)| pad(cell, width, align))
+        .collect()
+}
+
+fn rule(width: usize, align: Align) -> String {
+    match align {
+        Align::Left => format!(":{}", "-".repeat(width - 1)),
+        Align::Center => format!(":{}:", "-".repeat(width - 2)),
+        Align::Right => format!("{}:", "-".repeat(width - 1)),
+    }
+}
+
+fn csv(header: &[String], body: &[Vec<String>]) -> String {
+    let field = |cell: &String| {
+        if cell.contains([',', '"', '\n', '\r']) || cell.trim() != cell {
+            format!("\"{}\"", cell.replace('"', "\"\""))
+        } else {
+            cell.clone()
+        }
+    };
+    let mut table = String::new();
+    for row in std::iter::once(header).chain(body.iter().map(Vec::as_slice)) {
+        let fields: Vec<String> = row.iter().map(field).collect();
+        table.push_str(&fields.join(","));
+        table.push('\n');
+    }
+    table
+}
+
+fn widths(header: &[String], body: &[Vec<String>]) -> Vec<usize> {
+    header.iter().enumerate()
+        .map(|(index, cell)| {
+            body.iter().map(|row| display_width(&row[index])).fold(display_width(cell), usize::max)
+        })
+        .collect()
+}
+
+fn pad(cell: &str, width: usize, align: Align) -> String {
+    let padding = width.saturating_sub(display_width(cell));
+    let (before, after) = match align {
+        Align::Left => (0, padding),
+        Align::Center => (padding / 2, padding - padding / 2),
+        Align::Right => (padding, 0),
+    };
+    format!("{}{}{}", " ".repeat(before), cell, " ".repeat(after))
+}
+
+/*
+The number of terminal columns `text` takes: combining marks and other
+zero-width characters take none, East Asian wide characters and emoji take
+two, and everything else one, like `°` that takes two bytes.
+ */
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0000..=0x001F | 0x007F..=0x009F => 0,
+        0x0300..=0x036F | 0x0483..=0x0489 | 0x0591..=0x05BD | 0x1AB0..=0x1AFF | 0x1DC0..=0x1DFF => 0,
+        0x200B..=0x200F | 0x20D0..=0x20FF | 0xFE00..=0xFE0F | 0xFE20..=0xFE2F | 0xFEFF => 0,
+        0x1100..=0x115F | 0x2E80..=0x303E | 0x3041..=0x33FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF => 2,
+        0xA000..=0xA4CF | 0xAC00..=0xD7A3 | 0xF900..=0xFAFF | 0xFE30..=0xFE4F | 0xFF00..=0xFF60 => 2,
+        0xFFE0..=0xFFE6 | 0x1F300..=0x1F64F | 0x1F900..=0x1F9FF | 0x20000..=0x2FFFD | 0x30000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
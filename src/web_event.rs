@@ -0,0 +1,430 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::c_custom_types::WebEvent;
+use crate::json::{self, Value};
+
+/*
+Wire formats for the `WebEvent`s of `c_custom_types`. The text form is one
+event per line, written the way `inspect` prints them:
+
+    page loaded
+    page unloaded
+    pressed 'x'.
+    pasted "my text".
+    clicked at x=20, y=80.
+
+with the quotes, backslashes and control characters of keys and pastes
+escaped as in Rust, like `pasted "two\nlines".`, so that every event stays
+on its line. The JSON form is an object tagged with the type of the event:
+
+    {"type":"page_load"}
+    {"type":"page_unload"}
+    {"type":"key_press","key":"x"}
+    {"type":"paste","text":"my text"}
+    {"type":"click","x":20,"y":80}
+
+JSON numbers are doubles, which hold integers exactly only up to 2^53, so
+coordinates beyond that are written as strings, `{"type":"click","x":
+"-9223372036854775808","y":0}`, and read back from them.
+ */
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    Syntax { line: usize, column: usize, message: String },
+    Json(json::Error),
+    /*
+    Valid JSON that is not an event.
+     */
+    Event(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Syntax { line, column, message } => write!(f, "line {}, column {}: {}", line, column, message),
+            Error::Json(err) => write!(f, "{}", err),
+            Error::Event(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/*
+The kinds of `WebEvent`, named by the `type` of the JSON form, like
+`key_press`.
+ */
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    PageLoad,
+    PageUnload,
+    KeyPress,
+    Paste,
+    Click,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 5] = [Kind::PageLoad, Kind::PageUnload, Kind::KeyPress, Kind::Paste, Kind::Click];
+
+    pub fn of(event: &WebEvent) -> Kind {
+        match event {
+            WebEvent::PageLoad => Kind::PageLoad,
+            WebEvent::PageUnload => Kind::PageUnload,
+            WebEvent::KeyPress(_) => Kind::KeyPress,
+            WebEvent::Paste(_) => Kind::Paste,
+            WebEvent::Click { .. } => Kind::Click,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Kind::PageLoad => "page_load",
+            Kind::PageUnload => "page_unload",
+            Kind::KeyPress => "key_press",
+            Kind::Paste => "paste",
+            Kind::Click => "click",
+        }
+    }
+
+    /*
+    The fields of the JSON form besides `type`.
+     */
+    fn fields(self) -> &'static [&'static str] {
+        match self {
+            Kind::PageLoad | Kind::PageUnload => &[],
+            Kind::KeyPress => &["key"],
+            Kind::Paste => &["text"],
+            Kind::Click => &["x", "y"],
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Kind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Kind, String> {
+        Kind::ALL.into_iter().find(|kind| kind.name() == name).ok_or_else(|| {
+            let names: Vec<String> = Kind::ALL.iter().map(|kind| format!("`{}`", kind)).collect();
+            let (last, rest) = names.split_last().unwrap();
+            format!("unknown event type `{}`, expected {} or {}", name, rest.join(", "), last)
+        })
+    }
+}
+
+impl fmt::Display for WebEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WebEvent::PageLoad => write!(f, "page loaded"),
+            WebEvent::PageUnload => write!(f, "page unloaded"),
+            WebEvent::KeyPress(c) => write!(f, "pressed '{}'.", escape(&c.to_string(), '\'')),
+            WebEvent::Paste(s) => write!(f, "pasted \"{}\".", escape(s, '"')),
+            WebEvent::Click { x, y } => write!(f, "clicked at x={}, y={}.", x, y),
+        }
+    }
+}
+
+impl FromStr for WebEvent {
+    type Err = Error;
+
+    fn from_str(line: &str) -> Result<WebEvent, Error> {
+        from_text(line)
+    }
+}
+
+pub fn to_text(event: &WebEvent) -> String {
+    event.to_string()
+}
+
+pub fn from_text(line: &str) -> Result<WebEvent, Error> {
+    parse_line(line, 1)
+}
+
+/*
+One event per line, each ended by a newline.
+ */
+pub fn write_text(events: &[WebEvent]) -> String {
+    events.iter().map(|event| format!("{}\n", event)).collect()
+}
+
+/*
+Reads the events of every line, skipping blank ones.
+ */
+pub fn read_text(text: &str) -> Result<Vec<WebEvent>, Error> {
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| parse_line(line, index + 1))
+        .collect()
+}
+
+pub fn to_json(event: &WebEvent) -> Value {
+    let fields = match event {
+        WebEvent::PageLoad | WebEvent::PageUnload => Vec::new(),
+        WebEvent::KeyPress(c) => vec![Value::from(c.to_string())],
+        WebEvent::Paste(s) => vec![Value::from(s.as_str())],
+        WebEvent::Click { x, y } => vec![coordinate_to_json(*x), coordinate_to_json(*y)],
+    };
+    let kind = Kind::of(event);
+    let entries = kind.fields().iter().copied().zip(fields);
+    Value::object([("type", Value::from(kind.name()))].into_iter().chain(entries))
+}
+
+pub fn from_json(value: &Value) -> Result<WebEvent, Error> {
+    let Value::Object(entries) = value else {
+        return Err(Error::Event(format!("expected an event object, found {}", value.kind())));
+    };
+    let kind: Kind = match value.get("type") {
+        Some(Value::String(event_type)) => event_type.parse().map_err(Error::Event)?,
+        Some(other) => return Err(Error::Event(format!("`type` must be a string, found {}", other.kind()))),
+        None => return Err(Error::Event("missing field `type`".to_owned())),
+    };
+    if let Some((name, _)) = entries.iter().find(|(name, _)| name != "type" && !kind.fields().contains(&name.as_str())) {
+        return Err(Error::Event(format!("unknown field `{}` for a `{}` event", name, kind)));
+    }
+    let field = |name: &str| {
+        value.get(name).ok_or_else(|| Error::Event(format!("missing field `{}` for a `{}` event", name, kind)))
+    };
+
+    match kind {
+        Kind::PageLoad => Ok(WebEvent::PageLoad),
+        Kind::PageUnload => Ok(WebEvent::PageUnload),
+        Kind::KeyPress => {
+            let found = match field("key")? {
+                Value::String(key) => match key.chars().count() {
+                    0 => "an empty string".to_owned(),
+                    1 => return Ok(WebEvent::KeyPress(key.chars().next().unwrap())),
+                    count => format!("a string of {} characters", count),
+                },
+                other => other.kind().to_owned(),
+            };
+            Err(Error::Event(format!("`key` must be a string of one character, found {}", found)))
+        }
+        Kind::Paste => match field("text")? {
+            Value::String(text) => Ok(WebEvent::Paste(text.clone())),
+            other => Err(Error::Event(format!("`text` must be a string, found {}", other.kind()))),
+        },
+        Kind::Click => Ok(WebEvent::Click { x: coordinate("x", field("x")?)?, y: coordinate("y", field("y")?)? }),
+    }
+}
+
+pub fn write_json(event: &WebEvent) -> String {
+    to_json(event).to_string()
+}
+
+pub fn read_json(text: &str) -> Result<WebEvent, Error> {
+    from_json(&json::parse(text).map_err(Error::Json)?)
+}
+
+/*
+The largest integer a double holds exactly, along with all below it.
+ */
+const MAX_EXACT: f64 = 9_007_199_254_740_991.0;
+
+fn coordinate_to_json(value: i64) -> Value {
+    if value.unsigned_abs() <= MAX_EXACT as u64 {
+        Value::Number(value as f64)
+    } else {
+        Value::String(value.to_string())
+    }
+}
+
+/*
+Each coordinate has one form: a number up to `MAX_EXACT`, and a string of
+the integer beyond it.
+ */
+fn coordinate(name: &str, value: &Value) -> Result<i64, Error> {
+    match value {
+        Value::Number(number) if number.fract() == 0.0 && number.abs() <= MAX_EXACT => Ok(*number as i64),
+        Value::Number(number) => Err(Error::Event(format!(
+            "`{}` must be an integer between -{2} and {2}, found {1}",
+            name, number, MAX_EXACT
+        ))),
+        Value::String(text) => match text.parse::<i64>() {
+            Ok(integer) if integer.unsigned_abs() > MAX_EXACT as u64 => Ok(integer),
+            _ => Err(Error::Event(format!(
+                "`{}` must be a number, or a string of an integer beyond -{2} or {2}, found {1:?}",
+                name, text, MAX_EXACT
+            ))),
+        },
+        other => Err(Error::Event(format!("`{}` must be a number, found {}", name, other.kind()))),
+    }
+}
+
+fn escape(text: &str, quote: char) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\0' => out.push_str("\\0"),
+            c if c == quote => {
+                out.push('\\');
+                out.push(c);
+            }
+            c if c.is_control() => out.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+struct Cursor<'a> {
+    text: &'a str,
+    pos: usize,
+    line: usize,
+}
+
+impl Cursor<'_> {
+    fn rest(&self) -> &str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn error(&self, message: String) -> Error {
+        self.error_at(self.pos, message)
+    }
+
+    fn error_at(&self, pos: usize, message: String) -> Error {
+        Error::Syntax { line: self.line, column: self.text[..pos].chars().count() + 1, message }
+    }
+
+    fn unexpected(&self, expected: &str) -> Error {
+        match self.peek() {
+            Some(c) => self.error(format!("expected {}, found `{}`", expected, c)),
+            None => self.error(format!("expected {}, found the end of the line", expected)),
+        }
+    }
+
+    fn eat(&mut self, prefix: &str) -> bool {
+        let eaten = self.rest().starts_with(prefix);
+        if eaten {
+            self.pos += prefix.len();
+        }
+        eaten
+    }
+
+    fn expect(&mut self, prefix: &str) -> Result<(), Error> {
+        if self.eat(prefix) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("`{}`", prefix)))
+        }
+    }
+
+    fn integer(&mut self) -> Result<i64, Error> {
+        let start = self.pos;
+        self.eat("-");
+        let digits = self.rest().bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return Err(self.unexpected("an integer"));
+        }
+        self.pos += digits;
+        let text = &self.text[start..self.pos];
+        text.parse().map_err(|_| self.error_at(start, format!("`{}` does not fit into `i64`", text)))
+    }
+
+    /*
+    One character between quotes, escaped as by `escape`.
+     */
+    fn char(&mut self, quote: char) -> Result<char, Error> {
+        match self.peek() {
+            None => Err(self.error(format!("expected a closing `{}`", quote))),
+            Some('\\') => {
+                self.pos += 1;
+                let c = match self.peek() {
+                    Some('\\') => '\\',
+                    Some('n') => '\n',
+                    Some('r') => '\r',
+                    Some('t') => '\t',
+                    Some('0') => '\0',
+                    Some('u') if self.rest().starts_with("u{") => return self.unicode_escape(),
+                    Some(c) if c == quote => c,
+                    _ => return Err(self.unexpected("an escape like `\\n`, `\\\\` or `\\u{e9}`")),
+                };
+                self.pos += 1;
+                Ok(c)
+            }
+            Some(c) => {
+                self.pos += c.len_utf8();
+                Ok(c)
+            }
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, Error> {
+        let start = self.pos - 1;
+        self.pos += 2;
+        let digits = self.rest().chars().take_while(char::is_ascii_hexdigit).count();
+        let hex = &self.text[self.pos..self.pos + digits];
+        self.pos += digits;
+        let c = u32::from_str_radix(hex, 16).ok().filter(|_| digits <= 6).and_then(char::from_u32);
+        match c {
+            Some(c) if self.eat("}") => Ok(c),
+            Some(_) => Err(self.unexpected("`}`")),
+            None => Err(self.error_at(start, format!("`\\u{{{}}}` is not a character", hex))),
+        }
+    }
+
+    fn quoted(&mut self, quote: char) -> Result<String, Error> {
+        let mut text = String::new();
+        loop {
+            if self.peek() == Some(quote) {
+                self.pos += quote.len_utf8();
+                return Ok(text);
+            }
+            text.push(self.char(quote)?);
+        }
+    }
+}
+
+fn parse_line(text: &str, line: usize) -> Result<WebEvent, Error> {
+    let mut cursor = Cursor { text, pos: 0, line };
+
+    let event = if cursor.eat("page loaded") {
+        WebEvent::PageLoad
+    } else if cursor.eat("page unloaded") {
+        WebEvent::PageUnload
+    } else if cursor.eat("pressed ") {
+        cursor.expect("'")?;
+        if cursor.peek() == Some('\'') {
+            return Err(cursor.error("expected a key between the quotes".to_owned()));
+        }
+        let key = cursor.char('\'')?;
+        cursor.expect("'")?;
+        cursor.expect(".")?;
+        WebEvent::KeyPress(key)
+    } else if cursor.eat("pasted ") {
+        cursor.expect("\"")?;
+        let text = cursor.quoted('"')?;
+        cursor.expect(".")?;
+        WebEvent::Paste(text)
+    } else if cursor.eat("clicked at ") {
+        cursor.expect("x=")?;
+        let x = cursor.integer()?;
+        cursor.expect(", y=")?;
+        let y = cursor.integer()?;
+        cursor.expect(".")?;
+        WebEvent::Click { x, y }
+    } else {
+        return Err(cursor.error(
+            "expected an event, like `page loaded`, `page unloaded`, `pressed 'x'.`, `pasted \"my text\".` or `clicked at x=20, y=80.`"
+                .to_owned(),
+        ));
+    };
+
+    match cursor.rest() {
+        "" => Ok(event),
+        rest => Err(cursor.error(format!("unexpected `{}` after the event", rest))),
+    }
+}
@@ -0,0 +1,148 @@
+use rust_by_example::bits::{eval, Error, Int, IntType, INT_TYPES, MAX_DEPTH};
+
+fn samples() -> Vec<i128> {
+    let mut samples = vec![0, 1, -1, 2, -2, 5, 127, -128, 128, 255, 256, -32768, 65535];
+    samples.extend([i32::MAX as i128, i32::MIN as i128, u32::MAX as i128, i64::MIN as i128, u64::MAX as i128]);
+    samples.extend([i128::MAX, i128::MIN, 0x0123_4567_89ab_cdef_0011_2233_4455_6677]);
+    samples
+}
+
+#[test]
+fn converts_every_width_between_every_base() {
+    for int_type in INT_TYPES {
+        for value in samples() {
+            let Some(int) = Int::new(int_type, value) else { continue };
+            assert_eq!(int.to_i128(), Some(value));
+            for radix in 2..=36 {
+                let text = int.to_radix(radix).unwrap();
+                assert_eq!(Int::from_radix(&text, radix, int_type), Ok(int), "{} in base {}", value, radix);
+            }
+            assert_eq!(int.to_radix(10).unwrap(), value.to_string());
+            assert_eq!(int.to_string(), value.to_string());
+        }
+    }
+
+    let max = Int::from(u128::MAX);
+    assert_eq!(max.to_radix(16).unwrap(), "f".repeat(32));
+    assert_eq!(max.to_radix(36).unwrap(), "f5lxx1zz5pnorynqglhzmsp33");
+    assert_eq!(Int::from_radix(&u128::MAX.to_string(), 10, IntType::U128), Ok(max));
+}
+
+#[test]
+fn matches_the_formatting_of_std() {
+    assert_eq!(Int::from(-1i8).to_radix(2).unwrap(), "-1");
+    assert_eq!(Int::from(0x80u32).to_radix(16).unwrap(), format!("{:x}", 0x80u32));
+    assert_eq!(Int::from(i64::MIN).to_radix(8).unwrap(), format!("-{:o}", i64::MIN.unsigned_abs()));
+    assert_eq!(format!("{:>6}", Int::from(-42i16)), format!("{:>6}", -42i16));
+    assert_eq!(format!("{:+}", Int::from(7u8)), "+7");
+}
+
+#[test]
+fn shows_twos_complement_layouts() {
+    assert_eq!(Int::from(-2i8).layout(4), "1111 1110");
+    assert_eq!(Int::from(0x80u16).layout(8), "00000000 10000000");
+    assert_eq!(Int::from(5u8).layout(0), "00000101");
+    assert_eq!(Int::from(1u8).layout(3), "00 000 001");
+
+    for value in samples() {
+        if let Ok(value) = i32::try_from(value) {
+            let layout = Int::from(value).layout(0);
+            assert_eq!(layout, format!("{:032b}", value));
+        }
+    }
+}
+
+#[test]
+fn rejects_what_does_not_fit() {
+    assert_eq!(Int::new(IntType::U8, 256), None);
+    assert_eq!(Int::new(IntType::I8, -129), None);
+    assert_eq!(Int::new(IntType::U128, -1), None);
+    assert_eq!(Int::from_radix("-1", 10, IntType::U8), Err(Error::OutOfRange { literal: "-1".to_owned(), int_type: IntType::U8 }));
+    assert_eq!(Int::from_radix("80", 16, IntType::I8).unwrap_err().to_string(), "`80` is out of range for `i8`");
+    assert_eq!(Int::from_radix("-80", 16, IntType::I8), Ok(Int::from(i8::MIN)));
+    assert_eq!(Int::from_radix("12", 2, IntType::U8).unwrap_err().to_string(), "column 2: `2` is not a digit in base 2");
+    assert_eq!(Int::from(1u8).to_radix(37), Err(Error::Radix(37)));
+    assert_eq!(Int::from(300i32).convert(IntType::U8), None);
+    assert_eq!(Int::from(-3i32).convert(IntType::I8), Some(Int::from(-3i8)));
+}
+
+#[test]
+fn evaluates_the_examples_of_literals_and_operators() {
+    assert_eq!(eval("0b0011u32 & 0b0101", None), Ok(Int::from(0b0001u32)));
+    assert_eq!(eval("0b0011u32 | 0b0101", None), Ok(Int::from(0b0111u32)));
+    assert_eq!(eval("0b0011u32 ^ 0b0101", None), Ok(Int::from(0b0110u32)));
+    assert_eq!(eval("1u32 << 5", None), Ok(Int::from(32u32)));
+    assert_eq!(eval("0x80u32 >> 2", None), Ok(Int::from(0x20u32)));
+    assert_eq!(eval("1_000_000u32", None), Ok(Int::from(1_000_000u32)));
+    assert_eq!(eval("1 - 2", None), Ok(Int::from(-1i32)));
+}
+
+#[test]
+fn follows_the_precedence_of_rust() {
+    let cases: [(&str, i64); 10] = [
+        ("1 + 2 * 3", 1 + 2 * 3),
+        ("(1 + 2) * 3", (1 + 2) * 3),
+        ("1 << 2 + 1", 1 << (2 + 1)),
+        ("6 & 3 ^ 1 | 8", ((6 & 3) ^ 1) | 8),
+        ("-7 / 2", -7 / 2),
+        ("-7 % 3", -7 % 3),
+        ("!5 & 0xf0", !5 & 0xf0),
+        ("-16 >> 2", -16 >> 2),
+        ("- -5", 5),
+        ("0o17 + 0x1F - 0b11", 0o17 + 0x1F - 0b11),
+    ];
+    for (expression, expected) in cases {
+        assert_eq!(eval(expression, Some(IntType::I64)), Ok(Int::from(expected)), "{}", expression);
+    }
+    assert_eq!(eval("0xF0u8 >> 4", None), Ok(Int::from(0x0Fu8)));
+    assert_eq!(eval("0xF0i16 << 12", None), Ok(Int::from(0xF0i16 << 12)));
+}
+
+#[test]
+fn reports_overflow_like_a_debug_build() {
+    let error = |expression: &str| eval(expression, None).unwrap_err().to_string();
+
+    assert_eq!(error("255u8 + 1"), "`255 + 1` in `u8`: attempt to add with overflow");
+    assert_eq!(error("1u32 - 2"), "`1 - 2` in `u32`: attempt to subtract with overflow");
+    assert_eq!(error("0x10000 * 0x10000"), "`65536 * 65536` in `i32`: attempt to multiply with overflow");
+    assert_eq!(error("-128i8 / -1"), "`-128 / -1` in `i8`: attempt to divide with overflow");
+    assert_eq!(error("5 / (2 - 2)"), "`5 / 0` in `i32`: attempt to divide by zero");
+    assert_eq!(error("5 % 0"), "`5 % 0` in `i32`: attempt to calculate the remainder with a divisor of zero");
+    assert_eq!(error("1u32 << 32"), "`1 << 32` in `u32`: attempt to shift left with overflow");
+    assert_eq!(error("1 >> -1"), "`1 >> -1` in `i32`: attempt to shift right with overflow");
+    assert_eq!(error("-(-128i8)"), "`-(-128)` in `i8`: attempt to negate with overflow");
+    assert_eq!(eval("340282366920938463463374607431768211455u128 + 0", None), Ok(Int::from(u128::MAX)));
+    assert_eq!(error("340282366920938463463374607431768211455u128 + 1"), format!("`{} + 1` in `u128`: attempt to add with overflow", u128::MAX));
+}
+
+#[test]
+fn reports_literals_types_and_syntax() {
+    let error = |expression: &str| eval(expression, None).unwrap_err().to_string();
+
+    assert_eq!(error("256u8"), "`256` is out of range for `u8`");
+    assert_eq!(error("2147483648"), "`2147483648` is out of range for `i32`");
+    assert_eq!(eval("-2147483648", None), Ok(Int::from(i32::MIN)));
+    assert_eq!(error("-1u8"), "cannot apply unary operator `-` to type `u8`");
+    assert_eq!(error("1u8 + 1u16"), "mismatched types: expected `u8`, found `u16`");
+    assert_eq!(eval("1u8 + 1", Some(IntType::U8)), Ok(Int::from(2u8)));
+    assert_eq!(error("5u7"), "column 2: invalid suffix `u7` for number literal");
+    assert_eq!(error("0b102"), "column 5: `2` is not a digit in base 2");
+    assert_eq!(error("1 +"), "column 4: unexpected end of the expression");
+    assert_eq!(error("(1 + 2"), "column 7: unexpected end of the expression");
+    assert_eq!(error("1 2"), "column 3: expected an operator");
+    assert_eq!(error("u8"), "column 1: unexpected `u`");
+    assert_eq!(error("~1"), "column 1: `~` is not an operator, use `!` to flip the bits");
+}
+
+#[test]
+fn rejects_expressions_nested_too_deeply() {
+    let parenthesized = |depth: usize| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+    assert_eq!(eval(&parenthesized(MAX_DEPTH), None), Ok(Int::from(1)));
+    assert_eq!(
+        eval(&parenthesized(MAX_DEPTH + 1), None).unwrap_err().to_string(),
+        format!("column {}: nested too deeply", MAX_DEPTH + 1)
+    );
+    assert_eq!(eval(&parenthesized(200_000), None).unwrap_err().to_string(), format!("column {}: nested too deeply", MAX_DEPTH + 1));
+    assert_eq!(eval(&format!("{}1", "!".repeat(MAX_DEPTH)), None), Ok(Int::from(1)));
+    assert_eq!(eval(&format!("{}1", "-!".repeat(100_000)), None).unwrap_err().to_string(), format!("column {}: nested too deeply", MAX_DEPTH + 1));
+}
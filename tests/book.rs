@@ -0,0 +1,45 @@
+use rust_by_example::book::{self, Format};
+use rust_by_example::registry;
+use rust_by_example::report;
+use rust_by_example::source;
+
+#[test]
+fn preamble_holds_the_items_defined_before_a_section() {
+    let chapter = registry::find("hello_world").unwrap();
+    let names: Vec<&str> = chapter.sections.iter().map(|section| section.name).collect();
+
+    let first = source::preamble(chapter.source, "intro", &names).unwrap();
+    let debug = source::preamble(chapter.source, "debug", &names).unwrap();
+
+    assert!(first.iter().all(|line| line.trim().is_empty()));
+    assert!(debug.contains(&"pub struct Person<'a> {"));
+    assert!(!debug.iter().any(|line| line.contains("fn format_print")));
+}
+
+#[test]
+fn markdown_pages_hold_prose_code_and_output() {
+    let report = report::collect(&registry::select("scoping_rules::raii_destructor"));
+
+    let pages = book::render(&report, Format::Markdown);
+
+    let files: Vec<&str> = pages.iter().map(|page| page.file.as_str()).collect();
+    assert_eq!(files, ["index.md", "scoping_rules.md"]);
+    assert!(pages[0].contents.contains("## [Scoping rules](scoping_rules.md)\n\n- Destructor\n"));
+
+    let chapter = &pages[1].contents;
+    assert!(chapter.starts_with("# Scoping rules\n\n## Destructor\n\n```rust\nstruct ToDrop;\n"));
+    assert!(chapter.contains("Output:\n\n```text\nMade a ToDrop\n`raii_destructor` function is finished\nToDrop is being dropped\n```\n"));
+}
+
+#[test]
+fn html_pages_are_escaped_and_link_sections() {
+    let report = report::collect(&registry::select("functions::calling_diverging_functions"));
+
+    let pages = book::render(&report, Format::Html);
+
+    assert!(pages[0].contents.contains("<a href=\"functions.html#calling_diverging_functions\">"));
+    let chapter = &pages[1].contents;
+    assert!(chapter.contains("<h2 id=\"calling_diverging_functions\">Calling a diverging function</h2>"));
+    assert!(chapter.contains("println!(&quot;Calling `foo()`&quot;);\nfoo();"));
+    assert!(chapter.contains("This section panics on purpose: &apos;This call never returns.&apos;"));
+}
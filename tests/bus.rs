@@ -0,0 +1,152 @@
+use std::cell::RefCell;
+use std::fs;
+use std::time::Duration;
+
+use rust_by_example::bus::{self, read_recording, Bus, Clock, Kind, ManualClock, Recorder, Replayer, Timed};
+use rust_by_example::c_custom_types::WebEvent;
+
+fn session() -> Vec<Timed> {
+    let at = Duration::from_millis;
+    vec![
+        Timed { at: at(0), event: WebEvent::PageLoad },
+        Timed { at: at(250), event: WebEvent::KeyPress('x') },
+        Timed { at: at(1_500), event: WebEvent::Click { x: 20, y: 80 } },
+        Timed { at: at(1_500), event: WebEvent::Paste("my text".to_owned()) },
+        Timed { at: at(4_000), event: WebEvent::PageUnload },
+    ]
+}
+
+#[test]
+fn handlers_get_the_kinds_they_subscribed_to() {
+    let seen = RefCell::new(Vec::new());
+    let mut bus = Bus::new();
+    bus.subscribe(Kind::Click, |event| seen.borrow_mut().push(format!("click handler: {}", event)));
+    let all = bus.subscribe_all(|event| seen.borrow_mut().push(format!("all: {}", event)));
+    bus.subscribe(Kind::KeyPress, |event| seen.borrow_mut().push(format!("key handler: {}", event)));
+
+    assert_eq!(bus.publish(&WebEvent::Click { x: 1, y: 2 }), 2);
+    assert_eq!(bus.publish(&WebEvent::KeyPress('q')), 2);
+    assert_eq!(bus.publish(&WebEvent::PageLoad), 1);
+    assert!(bus.unsubscribe(all));
+    assert!(!bus.unsubscribe(all));
+    assert_eq!(bus.publish(&WebEvent::PageUnload), 0);
+    drop(bus);
+
+    assert_eq!(
+        seen.into_inner(),
+        [
+            "click handler: clicked at x=1, y=2.",
+            "all: clicked at x=1, y=2.",
+            "all: pressed 'q'.",
+            "key handler: pressed 'q'.",
+            "all: page loaded",
+        ]
+    );
+    assert_eq!(Kind::of(&WebEvent::Paste(String::new())), Kind::Paste);
+    assert_eq!("key_press".parse(), Ok(Kind::KeyPress));
+    assert!("scroll".parse::<Kind>().is_err());
+}
+
+#[test]
+fn records_the_time_of_every_event() {
+    let clock = ManualClock::new();
+    clock.advance(Duration::from_secs(100));
+    let mut recorder = Recorder::new(Vec::new(), clock.clone());
+    let mut bus = Bus::new();
+    bus.subscribe_all(|event| recorder.record(event));
+
+    let mut now = Duration::ZERO;
+    for timed in session() {
+        clock.advance(timed.at - now);
+        now = timed.at;
+        bus.publish(&timed.event);
+    }
+    drop(bus);
+
+    let recording = String::from_utf8(recorder.finish().unwrap()).unwrap();
+    assert_eq!(
+        recording,
+        "\
+0.000 page loaded
+0.250 pressed 'x'.
+1.500 clicked at x=20, y=80.
+1.500 pasted \"my text\".
+4.000 page unloaded
+"
+    );
+    assert_eq!(read_recording(&recording), Ok(session()));
+}
+
+#[test]
+fn replays_at_the_original_or_a_faster_speed() {
+    for (speed, expected) in [(1.0, [0, 250, 1_500, 1_500, 4_000]), (4.0, [0, 62, 375, 375, 1_000])] {
+        let clock = ManualClock::new();
+        let times = RefCell::new(Vec::new());
+        let mut bus = Bus::new();
+        bus.subscribe_all(|_| times.borrow_mut().push(clock.now().as_millis()));
+
+        Replayer::new(clock.clone()).speed(speed).unwrap().replay(&session(), &mut bus);
+        drop(bus);
+        assert_eq!(times.into_inner(), expected, "at speed {}", speed);
+    }
+
+    let clock = ManualClock::new();
+    let mut events = Vec::new();
+    let mut bus = Bus::new();
+    bus.subscribe_all(|event| events.push(event.clone()));
+    Replayer::new(clock.clone()).speed(f64::INFINITY).unwrap().replay(&session(), &mut bus);
+    drop(bus);
+    assert_eq!(clock.now(), Duration::ZERO);
+    assert_eq!(events, session().into_iter().map(|timed| timed.event).collect::<Vec<_>>());
+}
+
+#[test]
+fn rejects_speeds_that_are_not_positive() {
+    let error = |speed: f64| Replayer::new(ManualClock::new()).speed(speed).err();
+
+    assert_eq!(error(0.0).as_deref(), Some("the speed of a replay must be positive, got 0"));
+    assert_eq!(error(-2.0).as_deref(), Some("the speed of a replay must be positive, got -2"));
+    assert_eq!(error(f64::NAN).as_deref(), Some("the speed of a replay must be positive, got NaN"));
+    assert_eq!(error(f64::NEG_INFINITY).as_deref(), Some("the speed of a replay must be positive, got -inf"));
+
+    let clock = ManualClock::new();
+    let times = RefCell::new(Vec::new());
+    let mut bus = Bus::new();
+    bus.subscribe_all(|_| times.borrow_mut().push(clock.now()));
+    Replayer::new(clock.clone()).speed(1e-300).unwrap().replay(&session(), &mut bus);
+    drop(bus);
+    assert_eq!(times.into_inner(), [Duration::ZERO, Duration::MAX, Duration::MAX, Duration::MAX, Duration::MAX]);
+}
+
+#[test]
+fn round_trips_through_a_file() {
+    let path = std::env::temp_dir().join(format!("rust-by-example-bus-{}.txt", std::process::id()));
+    let clock = ManualClock::new();
+    let mut recorder = Recorder::create(&path, clock.clone()).unwrap();
+    for timed in session() {
+        clock.advance(timed.at - clock.now());
+        recorder.record(&timed.event);
+    }
+    recorder.finish().unwrap();
+
+    let loaded = bus::load(&path);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), session());
+
+    let missing = bus::load(std::env::temp_dir().join("rust-by-example-bus-missing.txt"));
+    assert_eq!(missing.unwrap_err().kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn reports_malformed_recordings() {
+    let error = |text: &str| read_recording(text).unwrap_err().to_string();
+
+    assert_eq!(error("page loaded"), "line 1, column 1: `page` is not a time in seconds, like `1.250`");
+    assert_eq!(error("0.000"), "line 1, column 1: expected the seconds, a space and an event");
+    assert_eq!(error("-1 page loaded"), "line 1, column 1: `-1` is not a time in seconds, like `1.250`");
+    assert_eq!(error("0.5 page loaded\n\n1.25 clicked at x=1, y=2"), "line 3, column 25: expected `.`, found the end of the line");
+    assert_eq!(error("2 page loaded\n1 page unloaded"), "line 2, column 1: `1` is before `2.000`, the time of the event before");
+
+    let recording = read_recording("1 page loaded\n2.5 page unloaded").unwrap();
+    assert_eq!(recording.iter().map(|timed| timed.at.as_millis()).collect::<Vec<_>>(), [1_000, 2_500]);
+}
@@ -0,0 +1,121 @@
+use std::fs;
+
+use rust_by_example::golden::{self, Verdict};
+use rust_by_example::output::{self, Output};
+use rust_by_example::registry::{self, CHAPTERS};
+use rust_by_example::runner;
+
+#[test]
+fn registry_lists_every_chapter_in_book_order() {
+    let modules: Vec<&str> = CHAPTERS.iter().map(|chapter| chapter.module).collect();
+
+    assert_eq!(modules.len(), 15);
+    assert!(modules.windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(registry::find("flow_of_control").map(|chapter| chapter.module), Some("h_flow_of_control"));
+    assert_eq!(registry::find("o_scoping_rules").map(|chapter| chapter.name()), Some("scoping_rules"));
+}
+
+#[test]
+fn selectors_match_chapters_sections_and_globs() {
+    let paths = |selector| -> Vec<String> {
+        registry::select(selector).iter().map(|entry| entry.path()).collect()
+    };
+
+    assert_eq!(paths("flow_of_control::match_guards"), ["flow_of_control::match_guards"]);
+    assert_eq!(paths("primitives").len(), 4);
+    assert_eq!(paths("scoping_rules::lifetime_*").len(), 9);
+    assert_eq!(paths("*::intro").first().map(String::as_str), Some("hello_world::intro"));
+    assert!(paths("nothing::here").is_empty());
+}
+
+#[test]
+fn every_section_matches_its_annotated_output() {
+    for entry in registry::all() {
+        let outcome = runner::run_isolated(&entry);
+        let verdict = golden::verify(&entry, &outcome);
+
+        assert!(
+            matches!(verdict, Verdict::Pass | Verdict::Unannotated | Verdict::Skipped(_)),
+            "{}: {:?}\n{}",
+            entry.path(),
+            verdict,
+            outcome.captured.stdout()
+        );
+        assert_eq!(outcome.panic.is_some(), entry.section.panics, "{}", entry.path());
+    }
+}
+
+#[test]
+fn only_comments_below_printing_code_are_expected_output() {
+    let source = r#"
+fn section() {
+    let mut vec = Vec::new();
+    // At this point the compiler doesn't know the type of `vec`
+
+    vec.push(1);
+    println!("{:?}", vec); // printed on one line
+    // [1]
+
+    for i in 0..2 {
+        greet(i);
+    }
+    // hello 0
+    // hello 1
+
+    println!(
+        "{} and {}", "a", "b"
+    ); // a and b
+    unsafe {
+        println!("{}", undefined());
+        // 44
+    }
+}
+
+fn greet(i: u32) {
+    println!("hello {}", i);
+}
+"#;
+    let expected = golden::expected_output(source, "section");
+
+    assert_eq!(expected, ["[1]", "hello 0", "hello 1", "a and b"]);
+    let lines = |chapter: &str, section: &str| {
+        golden::expected_output(registry::find(chapter).unwrap().source, section)
+    };
+    assert!(lines("rust", "inference").iter().all(|line| !line.contains("compiler")));
+    assert!(lines("expressions", "expressions").iter().all(|line| !line.contains("semicolon")));
+    assert_eq!(lines("scoping_rules", "borrowing_aliasing")[2], "Point has coordinates: (5, 2, 1)");
+}
+
+#[test]
+fn panicking_section_reports_its_message_and_output() {
+    let entry = registry::select("functions::calling_diverging_functions")[0];
+
+    let outcome = runner::run_isolated(&entry);
+    let panic = outcome.panic.expect("`foo()` panics");
+
+    assert_eq!(panic.message, "This call never returns.");
+    assert!(panic.location.is_some_and(|location| location.starts_with("src/i_functions.rs")));
+    assert_eq!(outcome.captured.stdout(), "Calling `foo()`\n");
+}
+
+#[test]
+fn capture_keeps_stdout_and_stderr_apart() {
+    let entry = registry::select("hello_world::format_print")[0];
+
+    let ((), captured) = output::capture(entry.section.run);
+
+    assert_eq!(captured.stderr(), "[ERROR hello_world] Error print\n");
+    assert!(captured.stdout().starts_with("1 Month is 31 days\n"));
+}
+
+#[test]
+fn output_can_be_redirected_to_a_file() {
+    let path = std::env::temp_dir().join(format!("rust-by-example-{}.txt", std::process::id()));
+    let chapter = registry::find("expressions").unwrap();
+
+    output::redirect(Output::file(&path).unwrap(), || chapter.run());
+
+    let written = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(written, "x is 5\ny is 155\nz is ()\n");
+}
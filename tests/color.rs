@@ -0,0 +1,140 @@
+use rust_by_example::color::{Cmy, Cmyk, Error, Hsl, Hsv, Rgb};
+use rust_by_example::{a_hello_world, c_custom_types, h_flow_of_control};
+
+/*
+A deterministic sample of colors: the corners of the cube, the grays and a
+few thousand colors from a linear congruential generator.
+ */
+fn sample() -> Vec<Rgb> {
+    let bit = |corner: u32, mask: u32| if corner & mask == 0 { 0 } else { 255 };
+    let mut colors: Vec<Rgb> = (0..8).map(|corner| Rgb::new(bit(corner, 4), bit(corner, 2), bit(corner, 1))).collect();
+    colors.extend((0..=255).map(|gray| Rgb::new(gray, gray, gray)));
+
+    let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+    for _ in 0..5000 {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        colors.push(Rgb::from_u32((state >> 40) as u32));
+    }
+    colors
+}
+
+#[test]
+fn float_spaces_round_trip_exactly() {
+    for rgb in sample() {
+        assert_eq!(Rgb::from(Hsv::from(rgb)), rgb, "through HSV: {:?}", Hsv::from(rgb));
+        assert_eq!(Rgb::from(Hsl::from(rgb)), rgb, "through HSL: {:?}", Hsl::from(rgb));
+        assert_eq!(Rgb::from(Cmy::from(rgb)), rgb, "through CMY: {:?}", Cmy::from(rgb));
+        assert_eq!(Rgb::from(Cmyk::from(rgb)), rgb, "through CMYK: {:?}", Cmyk::from(rgb));
+    }
+}
+
+#[test]
+fn text_round_trips_exactly() {
+    for rgb in sample() {
+        assert_eq!(rgb.to_hex().parse::<Rgb>(), Ok(rgb));
+        assert_eq!(rgb.to_hex().to_uppercase().parse::<Rgb>(), Ok(rgb));
+        assert_eq!(rgb.to_css().parse::<Rgb>(), Ok(rgb));
+        assert_eq!(Rgb::from_u32(rgb.to_u32()), rgb);
+        if let Some(name) = rgb.name() {
+            assert_eq!(Rgb::named(name), Some(rgb));
+        }
+    }
+}
+
+#[test]
+fn hello_world_color_round_trips_exactly() {
+    for rgb in sample() {
+        let color = a_hello_world::Color::from(rgb);
+        assert_eq!(Rgb::from(&color), rgb);
+
+        let hex = color.to_string().rsplit_once(' ').unwrap().1.to_owned();
+        assert_eq!(hex.parse::<Rgb>(), Ok(rgb), "{}", color);
+    }
+}
+
+/*
+Whole degrees and percentages cannot hold every 8-bit channel, but a degree
+and half a percent are never off by more than a few steps of 255.
+ */
+#[test]
+fn flow_of_control_color_round_trips_closely() {
+    let close = |a: Rgb, b: Rgb| {
+        let distance = |x: u8, y: u8| x.abs_diff(y);
+        distance(a.red, b.red).max(distance(a.green, b.green)).max(distance(a.blue, b.blue))
+    };
+
+    for rgb in sample() {
+        let exact = h_flow_of_control::Color::from(rgb);
+        assert_eq!(Rgb::try_from(&exact), Ok(rgb));
+
+        let lossy = [
+            h_flow_of_control::Color::from(Hsv::from(rgb)),
+            h_flow_of_control::Color::from(Hsl::from(rgb)),
+            h_flow_of_control::Color::from(Cmy::from(rgb)),
+            h_flow_of_control::Color::from(Cmyk::from(rgb)),
+        ];
+        for color in &lossy {
+            let back = Rgb::try_from(color).unwrap();
+            assert!(close(back, rgb) <= 4, "{} came back as {}", rgb, back);
+        }
+    }
+}
+
+#[test]
+fn converts_the_examples_of_match_destructuring() {
+    use h_flow_of_control::Color::*;
+
+    assert_eq!(Rgb::try_from(&RGB(122, 17, 40)), Ok(Rgb::new(122, 17, 40)));
+    assert_eq!(Rgb::try_from(&HSV(0, 100, 100)), Ok(Rgb::new(255, 0, 0)));
+    assert_eq!(Rgb::try_from(&HSL(120, 100, 50)), Ok(Rgb::new(0, 255, 0)));
+    assert_eq!(Rgb::try_from(&CMY(100, 100, 0)), Ok(Rgb::new(0, 0, 255)));
+    assert_eq!(Rgb::try_from(&CMYK(0, 0, 0, 100)), Ok(Rgb::new(0, 0, 0)));
+    assert_eq!(Rgb::try_from(&Green), Ok(Rgb::new(0, 255, 0)));
+
+    assert_eq!(Rgb::try_from(&RGB(256, 0, 0)), Err(Error::OutOfRange { what: "red", value: 256, max: 255 }));
+    assert_eq!(Rgb::try_from(&HSV(360, 0, 0)), Err(Error::OutOfRange { what: "hue", value: 360, max: 359 }));
+    assert_eq!(Rgb::try_from(&CMYK(0, 0, 0, 101)), Err(Error::OutOfRange { what: "key", value: 101, max: 100 }));
+}
+
+#[test]
+fn custom_types_color_uses_its_discriminants() {
+    for (color, hex) in [
+        (c_custom_types::Color::Red, "#ff0000"),
+        (c_custom_types::Color::Green, "#00ff00"),
+        (c_custom_types::Color::Blue, "#0000ff"),
+    ] {
+        let rgb = Rgb::from(&color);
+        assert_eq!(rgb.to_string(), hex);
+        assert!(c_custom_types::Color::try_from(rgb).is_ok());
+    }
+
+    let navy = Rgb::named("navy").unwrap();
+    assert!(matches!(c_custom_types::Color::try_from(navy), Err(Error::NoVariant(rgb)) if rgb == navy));
+}
+
+#[test]
+fn parses_hex_css_and_names() {
+    let expected = Rgb::new(0, 3, 254);
+    for text in ["#0003fe", "#0003FE", "0x0003FE", "rgb(0,3,254)", "rgb(0, 3, 254)", "RGB( 0 3 254 )", " #0003fe "] {
+        assert_eq!(text.parse::<Rgb>(), Ok(expected), "{}", text);
+    }
+
+    assert_eq!("#f80".parse::<Rgb>(), Ok(Rgb::new(0xff, 0x88, 0x00)));
+    assert_eq!("Orange".parse::<Rgb>(), Ok(Rgb::new(255, 165, 0)));
+    assert_eq!("grey".parse::<Rgb>(), "gray".parse::<Rgb>());
+    assert_eq!(Rgb::new(0, 255, 255).name(), Some("aqua"));
+    assert_eq!(expected.name(), None);
+    assert_eq!(expected.to_css(), "rgb(0, 3, 254)");
+}
+
+#[test]
+fn reports_what_does_not_parse() {
+    let error = |text: &str| text.parse::<Rgb>().unwrap_err().to_string();
+
+    assert_eq!(error("#12345"), "invalid color: `#12345` is not a hex color");
+    assert_eq!(error("#gggggg"), "invalid color: `#gggggg` is not a hex color");
+    assert_eq!(error("rgb(1, 2)"), "invalid color: `rgb(1, 2)` needs three channels");
+    assert_eq!(error("rgb(1, x, 3)"), "invalid color: green `x` is not a number");
+    assert_eq!(error("rgb(1, 2, 300)"), "blue 300 is out of range, expected 0 to 255");
+    assert_eq!(error("chartreuse"), "invalid color: `chartreuse` is not a hex, `rgb()` or named color");
+}
@@ -0,0 +1,182 @@
+/*
+Several sections keep their lesson in commented-out lines that the prose
+claims do not compile. Each case below uncomments one such statement in a
+copy of its chapter, compiles the chapter with the local `rustc` next to
+the modules it uses, and checks that the first error it reports carries the
+expected code. Later errors may follow from the first one.
+ */
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use rust_by_example::registry::{self, Entry};
+
+macro_rules! compile_fail {
+    ($($test:ident: $path:literal, $marker:literal => $code:literal;)*) => {
+        $(
+            #[test]
+            fn $test() {
+                assert_compile_fail(stringify!($test), $path, $marker, $code);
+            }
+        )*
+    };
+}
+
+compile_fail! {
+    mutability: "variable_bindings::mutability", "immutable_binding += 1;" => "E0384";
+    scope: "variable_bindings::scope", "short_lived_binding);" => "E0425";
+    declare_first: "variable_bindings::declare_first", "a_binding);" => "E0381";
+    freezing: "variable_bindings::freezing", "_mutable_integer = 50;" => "E0384";
+    casting_implicit: "rust::casting", "let integer: u8 = decimal;" => "E0308";
+    casting_char: "rust::casting", "decimal as char;" => "E0604";
+    for_loops_into_iter: "flow_of_control::for_loops", "names(into_iter)" => "E0382";
+    immutable_method_receiver: "functions::associated_functions_and_methods", "rectangle.translate(1.0, 0.0);" => "E0596";
+    closure_moved_out: "functions::closures_capturing", "print();" => "E0505";
+    closure_reborrow: "functions::closures_capturing", "let _reborrow = &count;" => "E0502";
+    closure_move: "functions::closures_move", "vec1.len());" => "E0382";
+    visibility_restricted: "modules::visibility", "my_mod::nested::public_function_in_my_mod();" => "E0603";
+    visibility_private_module: "modules::visibility", "my_mod::private_nested::function();" => "E0603";
+    visibility_private_restricted: "modules::visibility", "my_mod::private_nested::restricted_function();" => "E0603";
+    struct_private_fields: "modules::struct_visibility", "let closed_box = my_box::ClosedBox" => "E0451";
+    struct_private_field_access: "modules::struct_visibility", "_closed_box.contents);" => "E0616";
+    bounds_debug: "generics::generics_bounds", "print_debug(&_triangle);" => "E0277";
+    bounds_has_area: "generics::generics_bounds", "_triangle.area());" => "E0599";
+    phantom_tuple: "generics::generics_phantom_type_params", "_tuple1 == _tuple2" => "E0308";
+    phantom_struct: "generics::generics_phantom_type_params", "_struct1 == _struct2" => "E0308";
+    moved_box: "scoping_rules::ownership_and_moves", "println!(\"a contains: {}\", a);" => "E0382";
+    destroyed_box: "scoping_rules::ownership_and_moves", "println!(\"b contains: {}\", b);" => "E0382";
+    immutable_box: "scoping_rules::ownership_and_movers_of_mutability", "*immutable_box = 4;" => "E0594";
+    partial_move: "scoping_rules::partial_moves", "println!(\"The person struct is {:?}\", person);" => "E0382";
+    borrowed_box: "scoping_rules::borrowing", "eat_box_i32(boxed_i32);" => "E0505";
+    immutable_book: "scoping_rules::borrowing_mutability", "new_edition(&mut immutabook);" => "E0596";
+    aliasing_mutable: "scoping_rules::borrowing_aliasing", "let mutable_borrow = &mut point;" => "E0502";
+    aliasing_immutable: "scoping_rules::borrowing_aliasing", "let y = &point.y;" => "E0502";
+    aliasing_print: "scoping_rules::borrowing_aliasing", "point.z);" => "E0502";
+    lifetime_too_short: "scoping_rules::lifetime_explicit_annotation", "let y: &'a i32 = &_x;" => "E0597";
+    static_bound: "scoping_rules::lifetime_static", "print_it(&i);" => "E0597";
+}
+
+#[test]
+fn chapters_compile_as_they_are() {
+    for chapter in registry::CHAPTERS {
+        let errors = compile("as_they_are", chapter.module, chapter.source);
+        assert!(errors.is_empty(), "{} does not compile:\n{}", chapter.module, errors.join("\n"));
+    }
+}
+
+fn assert_compile_fail(test: &str, path: &str, marker: &str, code: &str) {
+    let entries = registry::select(path);
+    assert_eq!(entries.len(), 1, "`{}` names no single section", path);
+    let entry = entries[0];
+
+    let source = uncomment(&entry, marker);
+    let errors = compile(test, entry.chapter.module, &source);
+
+    assert!(
+        errors.first().is_some_and(|error| error.contains(&format!("error[{}]", code))),
+        "uncommenting `{}` in {} should fail with {}, got:\n{}",
+        marker,
+        entry.path(),
+        code,
+        errors.join("\n")
+    );
+}
+
+/*
+Uncomments the commented-out statement containing `marker` inside the
+section's function. A statement spans several lines while its code ends in
+an open `(` or `,`, and ends at the first line whose code ends with `;`.
+ */
+fn uncomment(entry: &Entry, marker: &str) -> String {
+    let mut lines: Vec<String> = entry.chapter.source.lines().map(String::from).collect();
+    let signature = format!("fn {}(", entry.section.name);
+    let start = lines.iter().position(|line| line.starts_with(&signature)).unwrap();
+    let end = start + lines[start..].iter().position(|line| line.starts_with('}')).unwrap();
+
+    let commented = |line: &str| line.trim_start().starts_with("// ");
+    let found = (start..end)
+        .find(|&index| commented(&lines[index]) && lines[index].contains(marker))
+        .unwrap_or_else(|| panic!("no commented-out `{}` in {}", marker, entry.path()));
+
+    let mut first = found;
+    while first > start && commented(&lines[first - 1]) && opens(&code(&lines[first - 1])) {
+        first -= 1;
+    }
+    let mut index = first;
+    loop {
+        let line = &mut lines[index];
+        assert!(commented(line), "statement at `{}` ends early in {}", marker, entry.path());
+        *line = line.replacen("// ", "", 1);
+        if code(line).ends_with(';') {
+            break;
+        }
+        index += 1;
+    }
+    lines.join("\n")
+}
+
+fn code(line: &str) -> String {
+    let line = line.trim_start().strip_prefix("// ").unwrap_or(line.trim_start());
+    line.split(" // ").next().unwrap().trim_end().to_owned()
+}
+
+fn opens(code: &str) -> bool {
+    code.ends_with('(') || code.ends_with(',')
+}
+
+/*
+The modules of the crate that a chapter or the macros of `src/macros.rs`
+reach through `crate::` paths, and the ones those reach in turn.
+ */
+fn support(module: &str, source: &str) -> BTreeSet<String> {
+    let mut found = BTreeSet::new();
+    let mut pending = vec![source.to_owned(), fs::read_to_string(src("macros")).unwrap()];
+    while let Some(source) = pending.pop() {
+        for (index, _) in source.match_indices("crate::") {
+            let rest = &source[index + "crate::".len()..];
+            let name = &rest[..rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len())];
+            if name != module && src(name).is_file() && found.insert(name.to_owned()) {
+                pending.push(fs::read_to_string(src(name)).unwrap());
+            }
+        }
+    }
+    found
+}
+
+fn src(module: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join(format!("{}.rs", module))
+}
+
+/*
+Compiles `source` as the chapter module `module` of a small library crate
+and returns the error lines reported by `rustc`.
+ */
+fn compile(test: &str, module: &str, source: &str) -> Vec<String> {
+    let dir = std::env::temp_dir().join(format!("rust-by-example-{}-{}", std::process::id(), test));
+    fs::create_dir_all(&dir).unwrap();
+
+    let mut root = format!("#[macro_use]\n#[path = {:?}]\nmod macros;\n", src("macros"));
+    for support in support(module, source) {
+        root.push_str(&format!("#[path = {:?}]\npub mod {};\n", src(&support), support));
+    }
+    root.push_str(&format!("pub mod {};\n", module));
+    fs::write(dir.join("lib.rs"), root).unwrap();
+    fs::write(dir.join(format!("{}.rs", module)), source).unwrap();
+
+    let output = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned()))
+        .args(["--edition", "2021", "--crate-type", "lib", "--emit", "metadata"])
+        .args(["--cap-lints", "allow", "--error-format", "short", "--out-dir"])
+        .arg(&dir)
+        .arg(dir.join("lib.rs"))
+        .output()
+        .expect("failed to run rustc");
+    fs::remove_dir_all(&dir).unwrap();
+
+    String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .filter(|line| line.contains(": error"))
+        .map(String::from)
+        .collect()
+}
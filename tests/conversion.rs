@@ -0,0 +1,24 @@
+use rust_by_example::f_conversion::{Circle, EvenNumber, MyNumber};
+
+#[test]
+fn number_converts_from_and_into_i32() {
+    let from = MyNumber::from(30);
+    let into: MyNumber = 5.into();
+
+    assert_eq!(from.value, 30);
+    assert_eq!(into.value, 5);
+}
+
+#[test]
+fn only_even_numbers_convert() {
+    assert_eq!(EvenNumber::try_from(8), Ok(EvenNumber(8)));
+    assert_eq!(EvenNumber::try_from(5), Err(()));
+
+    let result: Result<EvenNumber, ()> = (-4).try_into();
+    assert_eq!(result, Ok(EvenNumber(-4)));
+}
+
+#[test]
+fn circle_converts_to_string() {
+    assert_eq!(Circle { radius: 6 }.to_string(), "Circle of radius 6");
+}
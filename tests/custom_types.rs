@@ -0,0 +1,145 @@
+use rust_by_example::c_custom_types::{
+    self, inspect, is_big, Color, LinkedList, Number, Operations, Pair, Point, Rectangle, WebEvent,
+};
+use rust_by_example::output;
+
+#[test]
+fn structures_hold_their_fields() {
+    let Pair(integer, decimal) = Pair(1, 0.1);
+    let rectangle = Rectangle {
+        top_left: Point { x: 10.3, y: 0.4 },
+        bottom_right: Point { x: 5.2, y: 0.4 },
+    };
+
+    assert_eq!((integer, decimal), (1, 0.1));
+    assert_eq!(rectangle.top_left.x - rectangle.bottom_right.x, 10.3 - 5.2);
+}
+
+#[test]
+fn c_like_enums_have_discriminants() {
+    assert_eq!(Number::Zero as i32, 0);
+    assert_eq!(Number::Two as i32, 2);
+    assert_eq!(Color::Red as i32, 0xff0000);
+    assert_eq!(Color::Blue as i32, 0x0000ff);
+}
+
+#[test]
+fn inspect_describes_each_web_event() {
+    let ((), captured) = output::capture(|| {
+        inspect(WebEvent::KeyPress('x'));
+        inspect(WebEvent::Paste("my text".to_owned()));
+        inspect(WebEvent::Click { x: 20, y: 80 });
+        inspect(WebEvent::PageLoad);
+        inspect(WebEvent::PageUnload);
+    });
+
+    assert_eq!(
+        captured.stdout(),
+        "pressed 'x'.\npasted \"my text\".\nclicked at x=20, y=80.\npage loaded\npage unloaded\n"
+    );
+}
+
+#[test]
+fn type_alias_runs_the_operation() {
+    assert_eq!(Operations::Add.run(3, 2), 5);
+    assert_eq!(Operations::Subtract.run(3, 2), 1);
+}
+
+#[test]
+fn linked_list_prepends_to_the_front() {
+    let list = LinkedList::new();
+    assert!(list.is_empty());
+    assert_eq!(list.stringify(), "Nil");
+
+    let list = list.prepend(1).prepend(2).prepend(3);
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.stringify(), "3, 2, 1, Nil");
+}
+
+#[test]
+fn linked_list_is_a_stack() {
+    let mut list = LinkedList::new();
+    assert_eq!(list.peek(), None);
+    list.push_front("a");
+    list.push_front("b");
+    assert_eq!(list.peek(), Some(&"b"));
+    *list.peek_mut().unwrap() = "c";
+
+    assert_eq!(list.pop_front(), Some("c"));
+    assert_eq!(list.pop_front(), Some("a"));
+    assert_eq!(list.pop_front(), None);
+    assert!(list.is_empty());
+}
+
+#[test]
+fn linked_list_collects_and_extends_in_order() {
+    let mut list: LinkedList<i32> = (1..=3).collect();
+    assert_eq!(list.to_string(), "1, 2, 3, Nil");
+    list.extend(vec![4, 5]);
+    assert_eq!(list.len(), 5);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+
+    for elem in &mut list {
+        *elem *= 10;
+    }
+    assert_eq!((&list).into_iter().sum::<i32>(), 150);
+    assert_eq!(list.into_iter().collect::<Vec<_>>(), [10, 20, 30, 40, 50]);
+
+    let mut empty = LinkedList::new();
+    empty.extend(Some('x'));
+    assert_eq!(empty, LinkedList::new().prepend('x'));
+}
+
+#[test]
+fn linked_list_reverses_in_place() {
+    let mut list = LinkedList::new().prepend(1).prepend(2).prepend(3);
+    list.reverse();
+    assert_eq!(list.to_string(), "1, 2, 3, Nil");
+    assert_eq!(list.stringify(), list.to_string());
+
+    let mut empty: LinkedList<u8> = LinkedList::new();
+    empty.reverse();
+    assert_eq!(empty.to_string(), "Nil");
+}
+
+#[test]
+fn linked_list_clones_compares_and_debugs() {
+    let list: LinkedList<String> = ["x", "y"].iter().map(|s| s.to_string()).collect();
+    let mut copy = list.clone();
+    assert_eq!(copy, list);
+    copy.push_front("w".to_owned());
+    assert_ne!(copy, list);
+    assert_eq!(format!("{:?}", list), "Node(\"x\", Node(\"y\", Nil))");
+}
+
+#[test]
+fn linked_list_of_ten_million_nodes_is_stack_safe() {
+    const LEN: u32 = 10_000_000;
+    let mut list = LinkedList::new();
+    for elem in 0..LEN {
+        list.push_front(elem);
+    }
+    assert_eq!(list.len(), LEN as usize);
+    assert_eq!(list.peek(), Some(&(LEN - 1)));
+
+    let text = list.to_string();
+    assert!(text.starts_with("9999999, 9999998, "));
+    assert!(text.ends_with(", 1, 0, Nil"));
+
+    let debug = format!("{:?}", list);
+    assert!(debug.starts_with("Node(9999999, Node(9999998, "));
+    assert!(debug.ends_with(&format!("Node(1, Node(0, Nil{}", ")".repeat(LEN as usize))));
+    assert_eq!(debug.len(), text.len() + "Node(".len() * LEN as usize + LEN as usize);
+
+    list.reverse();
+    assert_eq!(list.peek(), Some(&0));
+    assert_eq!(list, list.clone());
+    drop(list);
+}
+
+#[test]
+fn constants_are_shared() {
+    assert_eq!(c_custom_types::LANGUAGE, "Rust");
+    assert!(is_big(c_custom_types::THRESHOLD + 1));
+    assert!(!is_big(c_custom_types::THRESHOLD));
+}
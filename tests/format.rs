@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use rust_by_example::format::{format, Args, Error, Template, Value};
+
+#[test]
+fn renders_the_specifiers_of_format_print() {
+    let args = Args::new().with("Alice").with("Bob");
+    assert_eq!(
+        format("{0}, this is {1}. {1}, this is {0}", &args).unwrap(),
+        format!("{0}, this is {1}. {1}, this is {0}", "Alice", "Bob")
+    );
+
+    let args = Args::new()
+        .with_named("subject", "the quick brown fox")
+        .with_named("verb", "jumps over")
+        .with_named("object", "the lazy dog");
+    assert_eq!(format("{subject} {verb} {object}", &args).unwrap(), "the quick brown fox jumps over the lazy dog");
+
+    let args = Args::new().with(69420);
+    assert_eq!(format("{0:b} {0:o} {0:x} {0:X}", &args).unwrap(), "10000111100101100 207454 10f2c 10F2C");
+    assert_eq!(format("{0:#b} {0:#o} {0:#x}", &args).unwrap(), format!("{0:#b} {0:#o} {0:#x}", 69420));
+
+    let args = Args::new().with_named("number", 1).with_named("width", 5);
+    assert_eq!(format("{number:>5}|{number:0<5}|{number:0>width$}", &args).unwrap(), "    1|10000|00001");
+}
+
+#[test]
+fn matches_std_for_fill_sign_precision_and_exponents() {
+    let args = Args::new().with(-42).with(1.23456).with("hello").with('x').with(true);
+
+    assert_eq!(
+        format("[{0:+}] [{0:08}] [{1:+.2}] [{1:^10.3}] [{1:e}] [{2:*^9}] [{2:.3}] [{2:?}] [{3:?}] [{4:>6}]", &args).unwrap(),
+        format!(
+            "[{0:+}] [{0:08}] [{1:+.2}] [{1:^10.3}] [{1:e}] [{2:*^9}] [{2:.3}] [{2:?}] [{3:?}] [{4:>6}]",
+            -42, 1.23456, "hello", 'x', true
+        )
+    );
+    assert_eq!(
+        format("{:.*} {} {{literal}}", &Args::new().with(1).with(2.55).with("next")).unwrap(),
+        format!("{:.*} {} {{literal}}", 1, 2.55, "next")
+    );
+    assert_eq!(format("{:>1$}|{:°<4}", &Args::new().with("ab").with(4)).unwrap(), "  ab|4°°°");
+}
+
+#[test]
+fn binds_named_arguments_from_a_map() {
+    let mut values = HashMap::new();
+    values.insert("name".to_owned(), Value::from("Dublin"));
+    values.insert("lat".to_owned(), Value::from(53.347778));
+
+    let template = Template::parse("{name}: {lat:.3}°N").unwrap();
+
+    assert_eq!(template.render(&Args::from(values)).unwrap(), "Dublin: 53.348°N");
+}
+
+#[test]
+fn parse_errors_point_at_their_column() {
+    let error = |template| Template::parse(template).unwrap_err();
+
+    assert_eq!(error("ok }"), Error { column: 4, message: "unmatched `}`, use `}}` for a literal `}`".to_owned() });
+    assert_eq!(error("{0:q}").column, 4);
+    assert_eq!(error("{0:q}").message, "unknown format type `q`");
+    assert_eq!(error("ab {0 1}").column, 6);
+    assert_eq!(error("ab {:.}").column, 7);
+    assert_eq!(error("°° {").to_string(), "column 5: unterminated placeholder, expected `}`");
+    assert_eq!(error("{-}").message, "invalid argument, expected a position or a name");
+}
+
+#[test]
+fn binding_errors_point_at_their_placeholder() {
+    let args = Args::new().with("text").with(-1);
+
+    assert_eq!(format("{} {} {}", &args).unwrap_err().to_string(), "column 7: no argument at position 2, there are 2");
+    assert_eq!(format("ab {missing}", &args).unwrap_err().to_string(), "column 4: no argument named `missing`");
+    assert_eq!(format("{0:x}", &args).unwrap_err().message, "a string cannot be formatted with `{:x}`");
+    assert_eq!(format("{1:b}", &args).unwrap_err().message, "cannot format the negative number -1 as binary");
+    assert_eq!(
+        format("{1:0$}", &args).unwrap_err().message,
+        "a width or precision must be a non-negative integer, got String(\"text\")"
+    );
+}
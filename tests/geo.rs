@@ -0,0 +1,97 @@
+use rust_by_example::a_hello_world::City;
+use rust_by_example::geo::{Coordinate, Error};
+
+fn close(actual: f64, expected: f64, tolerance: f64) -> bool {
+    (actual - expected).abs() <= tolerance
+}
+
+#[test]
+fn parses_what_city_displays() {
+    let cities = [
+        City { name: "Dublin", lat: 53.34778, lon: -6.25972 },
+        City { name: "Oslo", lat: 59.95, lon: 10.75 },
+        City { name: "Vancouver", lat: 49.25, lon: -123.1 },
+    ];
+
+    for city in &cities {
+        let coordinate = Coordinate::try_from(city).unwrap();
+        let rendered = city.to_string();
+
+        assert_eq!(rendered, format!("{}: {}", city.name, coordinate));
+        let parsed: Coordinate = rendered.split_once(": ").unwrap().1.parse().unwrap();
+        assert_eq!(parsed.to_string(), coordinate.to_string());
+    }
+
+    let nowhere = City { name: "Nowhere", lat: 200.0, lon: 0.0 };
+    assert_eq!(
+        Coordinate::try_from(&nowhere).unwrap_err().to_string(),
+        "latitude 200 is out of range, expected -90 to 90 degrees"
+    );
+}
+
+#[test]
+fn parses_degrees_minutes_and_seconds() {
+    let dublin: Coordinate = "53°20'52\"N 6°15'35\"W".parse().unwrap();
+    assert!(close(dublin.lat(), 53.347778, 1e-6));
+    assert!(close(dublin.lon(), -6.259722, 1e-6));
+    assert_eq!(dublin.to_dms(), "53°20'52\"N 6°15'35\"W");
+
+    let primes: Coordinate = "33° 52′ 10″ S, 151° 12′ 30″ E".parse().unwrap();
+    assert_eq!(primes.to_dms(), "33°52'10\"S 151°12'30\"E");
+
+    let minutes_only: Coordinate = "10°30'E 45°N".parse().unwrap();
+    assert_eq!((minutes_only.lat(), minutes_only.lon()), (45.0, 10.5));
+}
+
+#[test]
+fn parses_signed_decimal_degrees() {
+    let dublin: Coordinate = "53.347778, -6.259722".parse().unwrap();
+    assert_eq!((dublin.lat(), dublin.lon()), (53.347778, -6.259722));
+    assert_eq!(dublin.to_string(), "53.348°N 6.260°W");
+
+    let spaced: Coordinate = "-33.87° 151.21°".parse().unwrap();
+    assert_eq!(spaced.to_string(), "33.870°S 151.210°E");
+
+    let exponents: Coordinate = "1E1, 2".parse().unwrap();
+    assert_eq!(exponents, "1e1, 2".parse().unwrap());
+    assert_eq!(exponents.to_string(), "10.000°N 2.000°E");
+}
+
+#[test]
+fn rejects_out_of_range_and_malformed_input() {
+    let error = |text: &str| text.parse::<Coordinate>().unwrap_err();
+
+    assert_eq!(error("91, 0"), Error::OutOfRange { what: "latitude", value: 91.0, range: "-90 to 90 degrees" });
+    assert_eq!(error("0°N 180.5°E"), Error::OutOfRange { what: "longitude", value: 180.5, range: "-180 to 180 degrees" });
+    assert_eq!(error("53°61'N 6°W").to_string(), "minutes 61 is out of range, expected 0 up to 60");
+    assert_eq!(error("53°20'60\"N 6°W"), Error::OutOfRange { what: "seconds", value: 60.0, range: "0 up to 60" });
+    assert_eq!(error("53.3°N 6.2°N").to_string(), "invalid coordinate: more than one latitude");
+    assert_eq!(error("53.3°N"), Error::Syntax("missing the longitude, marked `E` or `W`".to_owned()));
+    assert_eq!(error("53.3°N 6.2"), Error::Syntax("`6.2` has no hemisphere".to_owned()));
+    assert_eq!(error("-53.3°N 6.2°E"), Error::Syntax("`-53.3°` is negative, the hemisphere gives the sign".to_owned()));
+    assert_eq!(error("53°20N 6°W"), Error::Syntax("`20` has no unit in `53°20`".to_owned()));
+    assert_eq!(error("53\"20°N 6°W"), Error::Syntax("unexpected `°` in `53\"20°`".to_owned()));
+    assert_eq!(error("north, west"), Error::Syntax("`north` is not a number".to_owned()));
+    assert_eq!(error(""), Error::Syntax("empty input".to_owned()));
+    assert!(Coordinate::new(f64::NAN, 0.0).is_err());
+}
+
+#[test]
+fn computes_great_circle_distance_and_bearing() {
+    let lands_end: Coordinate = "50°03'59\"N 5°42'53\"W".parse().unwrap();
+    let john_o_groats: Coordinate = "58°38'38\"N 3°04'12\"W".parse().unwrap();
+
+    assert!(close(lands_end.distance(&john_o_groats), 968.9, 0.1), "{}", lands_end.distance(&john_o_groats));
+    assert!(close(lands_end.bearing(&john_o_groats), 9.1198, 1e-3), "{}", lands_end.bearing(&john_o_groats));
+    assert_eq!(lands_end.distance(&john_o_groats), john_o_groats.distance(&lands_end));
+    assert_eq!(lands_end.distance(&lands_end), 0.0);
+
+    let north_pole = Coordinate::new(90.0, 0.0).unwrap();
+    let south_pole = Coordinate::new(-90.0, 0.0).unwrap();
+    assert!(close(north_pole.distance(&south_pole), std::f64::consts::PI * 6371.0088, 1e-6));
+    assert_eq!(lands_end.bearing(&north_pole), 0.0);
+
+    let equator = Coordinate::new(0.0, 0.0).unwrap();
+    assert!(close(equator.bearing(&Coordinate::new(0.0, 10.0).unwrap()), 90.0, 1e-9));
+    assert!(close(equator.bearing(&Coordinate::new(0.0, -10.0).unwrap()), 270.0, 1e-9));
+}
@@ -0,0 +1,37 @@
+use rust_by_example::a_hello_world::{City, Color, MyList, Person, Point2D};
+
+#[test]
+fn person_is_pretty_printed_with_debug() {
+    let peter = Person { name: "Peter", age: 27 };
+
+    assert_eq!(format!("{:#?}", peter), "Person {\n    name: \"Peter\",\n    age: 27,\n}");
+}
+
+#[test]
+fn point_displays_its_coordinates() {
+    let point = Point2D { x: 3.3, y: 2.2 };
+
+    assert_eq!(point.to_string(), "x: 3.3, y: 2.2");
+}
+
+#[test]
+fn list_displays_each_item_with_its_index() {
+    assert_eq!(MyList(vec![1, 2, 3]).to_string(), "[0: 1, 1: 2, 2: 3]");
+    assert_eq!(MyList(vec![]).to_string(), "[]");
+}
+
+#[test]
+fn city_displays_cardinal_directions() {
+    let dublin = City { name: "Dublin", lat: 53.34778, lon: -6.25972 };
+    let sydney = City { name: "Sydney", lat: -33.87, lon: 151.21 };
+
+    assert_eq!(dublin.to_string(), "Dublin: 53.348°N 6.260°W");
+    assert_eq!(sydney.to_string(), "Sydney: 33.870°S 151.210°E");
+}
+
+#[test]
+fn color_displays_rgb_and_hex() {
+    let color = Color { red: 0, green: 3, blue: 254 };
+
+    assert_eq!(color.to_string(), "RGB (0, 3, 254) 0x0003FE");
+}
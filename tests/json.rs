@@ -0,0 +1,53 @@
+use rust_by_example::json::{parse, Value, MAX_DEPTH};
+
+#[test]
+fn parses_what_it_writes() {
+    let value = Value::object([
+        ("name", Value::from("hello_world")),
+        ("passed", Value::from(true)),
+        ("duration", Value::from(0.25)),
+        ("missing", Value::Null),
+        ("lines", Value::from(vec!["tab\there", "quote \" and \\", "\u{1}"])),
+        ("nested", Value::object([("empty", Value::Array(Vec::new())), ("none", Value::object(Vec::<(String, Value)>::new()))])),
+    ]);
+    assert_eq!(parse(&value.to_string()), Ok(value.clone()));
+    assert_eq!(parse(&value.pretty()), Ok(value.clone()));
+    assert_eq!(value.get("passed"), Some(&Value::Bool(true)));
+    assert_eq!(value.get("absent"), None);
+}
+
+#[test]
+fn follows_the_grammar() {
+    assert_eq!(parse(" -0.5e+2 "), Ok(Value::Number(-50.0)));
+    assert_eq!(parse("1E3"), Ok(Value::Number(1000.0)));
+    assert_eq!(parse(r#""\/\b\fé🦀""#), Ok(Value::from("/\u{8}\u{c}é🦀")));
+    assert_eq!(parse("[1,[2,[3]]]"), Ok(Value::from(vec![Value::from(1.0), Value::from(vec![Value::from(2.0), Value::from(vec![3.0])])])));
+}
+
+#[test]
+fn reports_lines_and_columns() {
+    let error = |text: &str| parse(text).unwrap_err().to_string();
+
+    assert_eq!(error(""), "line 1, column 1: expected a value, found the end");
+    assert_eq!(error("01"), "line 1, column 2: leading zeros are not allowed");
+    assert_eq!(error("1."), "line 1, column 3: expected a digit after `.`, found the end");
+    assert_eq!(error("[1 2]"), "line 1, column 4: expected `,` or `]`, found `2`");
+    assert_eq!(error("{\n  \"a\" 1\n}"), "line 2, column 7: expected `:`, found `1`");
+    assert_eq!(error("nul"), "line 1, column 1: unknown literal `nul`, expected `null`, `true` or `false`");
+    assert_eq!(error("\"tab\there\""), "line 1, column 5: control character '\\t' must be escaped");
+    assert_eq!(error(r#""\ud83e""#), "line 1, column 8: lone surrogate `\\ud83e`");
+    assert_eq!(error("\"open"), "line 1, column 6: unterminated string");
+    assert_eq!(error("{} {}"), "line 1, column 4: unexpected `{` after the value");
+}
+
+#[test]
+fn limits_the_nesting() {
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(parse(&nested(MAX_DEPTH)).is_ok());
+    assert_eq!(
+        parse(&nested(MAX_DEPTH + 1)).unwrap_err().to_string(),
+        format!("line 1, column {}: nesting too deep", MAX_DEPTH + 1)
+    );
+    assert_eq!(parse(&"[".repeat(100_000)).unwrap_err().message, "nesting too deep");
+    assert_eq!(parse(&"{\"a\":".repeat(100_000)).unwrap_err().message, "nesting too deep");
+}
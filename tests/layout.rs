@@ -0,0 +1,91 @@
+#![allow(dead_code)]
+
+use std::mem::{self, offset_of};
+
+use rust_by_example::c_custom_types::{LinkedList, Point};
+use rust_by_example::layout::{self, type_name, Layout, Niche};
+use rust_by_example::o_scoping_rules::Book;
+
+#[repr(C)]
+struct Header {
+    tag: u8,
+    length: u32,
+    flags: u16,
+}
+
+fn header() -> Layout {
+    Layout::of::<Header>()
+        .field::<u8>("tag", offset_of!(Header, tag))
+        .field::<u32>("length", offset_of!(Header, length))
+        .field::<u16>("flags", offset_of!(Header, flags))
+}
+
+#[test]
+fn draws_the_padding_of_a_c_struct() {
+    let header = header();
+    assert_eq!((header.size, header.align), (12, 4));
+    assert_eq!(header.padding(), vec![1..4, 10..12]);
+    assert_eq!(
+        header.diagram(),
+        "\
+Header: 12 bytes, aligned to 4
+   0  A...BBBB
+   8  CC..
+  A  tag      u8   0..1
+  .  padding       1..4
+  B  length   u32  4..8
+  C  flags    u16  8..10
+  .  padding       10..12
+"
+    );
+}
+
+#[test]
+fn describes_the_structs_of_the_chapters() {
+    let names: Vec<String> = layout::layouts().into_iter().map(|layout| layout.name).collect();
+    assert_eq!(names, ["Point", "Rectangle", "Person", "Book", "LinkedList::Node"]);
+
+    for layout in layout::layouts() {
+        let covered: usize = layout.fields.iter().map(|field| field.size).sum();
+        let padding: usize = layout.padding().iter().map(|bytes| bytes.len()).sum();
+        assert_eq!(covered + padding, layout.size, "{}", layout.name);
+        for pair in layout.fields.windows(2) {
+            assert!(pair[0].bytes().end <= pair[1].offset, "{} overlaps in {}", pair[0].name, layout.name);
+        }
+        for field in &layout.fields {
+            assert_eq!(field.offset % field.align, 0, "{} is misaligned in {}", field.name, layout.name);
+        }
+        assert_eq!(layout.diagram().lines().next(), Some(format!("{}: {} bytes, aligned to {}", layout.name, layout.size, layout.align).as_str()));
+    }
+
+    let book = layout::find("book").unwrap();
+    assert_eq!(book.size, mem::size_of::<Book>());
+    assert_eq!(book.padding().iter().map(|bytes| bytes.len()).sum::<usize>(), 4);
+    assert_eq!(layout::find("Point").unwrap().padding(), vec![]);
+    assert_eq!(layout::find("Person").unwrap().padding().iter().map(|bytes| bytes.len()).sum::<usize>(), 7);
+    assert_eq!(layout::find("linkedlist::node").unwrap().size, mem::size_of::<LinkedList<u32>>());
+    assert_eq!(layout::find("Square"), None);
+}
+
+#[test]
+fn finds_the_niches_of_enums() {
+    let niche = Niche::option::<Box<LinkedList<u32>>>();
+    assert!(niche.is_used());
+    assert_eq!(niche.to_string(), "`Option<Box<LinkedList<u32>>>` is 8 bytes, as big as `Box<LinkedList<u32>>`: `None` is stored in a niche");
+    assert!(Niche::of::<LinkedList<u32>, (u32, Box<LinkedList<u32>>)>("Nil").is_used());
+    assert!(Niche::option::<char>().is_used());
+
+    let tagged = Niche::option::<Point>();
+    assert!(!tagged.is_used());
+    assert_eq!(tagged.to_string(), "`Option<Point>` is 12 bytes, 4 more than `Point` for the tag");
+    assert_eq!(layout::niches().len(), 6);
+}
+
+#[test]
+fn shortens_type_names() {
+    assert_eq!(type_name::<Option<Box<LinkedList<u32>>>>(), "Option<Box<LinkedList<u32>>>");
+    assert_eq!(type_name::<(u32, Box<LinkedList<u32>>)>(), "(u32, Box<LinkedList<u32>>)");
+    assert_eq!(type_name::<&'static str>(), "&str");
+    assert_eq!(type_name::<Vec<std::collections::HashMap<String, [u8; 4]>>>(), "Vec<HashMap<String, [u8; 4]>>");
+    assert_eq!(type_name::<rust_by_example::a_hello_world::Person<'static>>(), "Person");
+}
@@ -0,0 +1,46 @@
+use std::collections::BTreeSet;
+
+use rust_by_example::a_hello_world::{MyList, Point2D};
+use rust_by_example::list::List;
+
+#[test]
+fn displays_like_my_list() {
+    let numbers = vec![1, 2, 3];
+    assert_eq!(List::new(&numbers).indexed().to_string(), MyList(numbers.clone()).to_string());
+    assert_eq!(List::new(&numbers).to_string(), "[1, 2, 3]");
+    assert_eq!(List::new(&Vec::<i32>::new()).indexed().to_string(), "[]");
+}
+
+#[test]
+fn takes_any_iterable_of_display_items() {
+    let words = ["alpha", "beta"];
+    assert_eq!(List::new(&words).separator(" | ").brackets("", "").to_string(), "alpha | beta");
+    assert_eq!(List::new(BTreeSet::from(['c', 'a', 'b'])).brackets("{", "}").to_string(), "{a, b, c}");
+    assert_eq!(List::new(1..=3).separator("; ").brackets("<", ">").to_string(), "<1; 2; 3>");
+
+    let points = [Point2D { x: 3.3, y: 2.2 }, Point2D { x: 0.0, y: -1.0 }];
+    assert_eq!(List::new(&points).indexed().separator("\n").brackets("", "").to_string(), "0: x: 3.3, y: 2.2\n1: x: 0, y: -1");
+}
+
+#[test]
+fn truncates_after_the_limit() {
+    assert_eq!(List::new(1..=100).limit(3).to_string(), "[1, 2, 3, ...and 97 more]");
+    assert_eq!(List::new(1..=3).limit(3).to_string(), "[1, 2, 3]");
+    assert_eq!(List::new(1..=3).limit(0).to_string(), "[...and 3 more]");
+    assert_eq!(List::new(0..0).limit(0).to_string(), "[]");
+    assert_eq!(List::new(10..20).indexed().limit(2).to_string(), "[0: 10, 1: 11, ...and 8 more]");
+    assert_eq!(List::new(0..).limit(3).to_string(), "[0, 1, 2, ...and over 1000 more]");
+    assert_eq!(List::new(0..1003).limit(3).to_string(), "[0, 1, 2, ...and 1000 more]");
+    assert_eq!(List::new((0..).map(|n| n * n)).limit(1).to_string(), "[0, ...and over 1000 more]");
+}
+
+#[test]
+fn nests_collections() {
+    let matrix = vec![vec![1, 2], vec![], vec![3, 4, 5]];
+    assert_eq!(List::new(&matrix).nested().to_string(), "[[1, 2], [], [3, 4, 5]]");
+    assert_eq!(List::new(&matrix).indexed().limit(2).nested().to_string(), "[0: [0: 1, 1: 2], 1: [], ...and 1 more]");
+
+    let cube = [[[1, 2], [3, 4]], [[5, 6], [7, 8]]];
+    let deep = List::new(cube.iter().map(|plane| List::new(plane).separator("; ").nested()));
+    assert_eq!(deep.to_string(), "[[[1; 2]; [3; 4]], [[5; 6]; [7; 8]]]");
+}
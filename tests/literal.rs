@@ -0,0 +1,136 @@
+use rust_by_example::bits::{Int, IntType, INT_TYPES};
+use rust_by_example::literal::{parse, parse_as, Error, Number, NumberType};
+
+fn integer(number: Number) -> i128 {
+    match number {
+        Number::Int(int) => int.to_i128().unwrap(),
+        number => panic!("{} is not an integer", number),
+    }
+}
+
+#[test]
+fn parses_the_literals_of_the_chapters() {
+    assert_eq!(parse("1_000_000u32"), Ok(Number::Int(Int::from(1_000_000u32))));
+    assert_eq!(parse("5i32"), Ok(Number::Int(Int::from(5i32))));
+    assert_eq!(parse("65.4321_f32"), Ok(Number::F32(65.4321_f32)));
+    assert_eq!(parse("4294967296i64"), Ok(Number::Int(Int::from(4294967296i64))));
+    assert_eq!(parse("1u8"), Ok(Number::Int(Int::from(1u8))));
+    assert_eq!(parse("3f32"), Ok(Number::F32(3.0)));
+    assert_eq!(parse("0b0011u32"), Ok(Number::Int(Int::from(0b0011u32))));
+    assert_eq!(parse("0x80u32"), Ok(Number::Int(Int::from(0x80u32))));
+    assert_eq!(parse("-1i8"), Ok(Number::Int(Int::from(-1i8))));
+}
+
+#[test]
+fn infers_the_types_of_unsuffixed_literals() {
+    assert_eq!(parse("7").unwrap().number_type(), NumberType::Int(IntType::I32));
+    assert_eq!(parse("3.0").unwrap().number_type(), NumberType::F64);
+    assert_eq!(parse("1e3"), Ok(Number::F64(1000.0)));
+    assert_eq!(parse("2."), Ok(Number::F64(2.0)));
+
+    assert_eq!(parse_as("12", NumberType::Int(IntType::I64)), Ok(Number::Int(Int::from(12i64))));
+    assert_eq!(parse_as("0.1", NumberType::F32), Ok(Number::F32(0.1)));
+    assert_eq!(parse_as("5u8", NumberType::Int(IntType::U8)), Ok(Number::Int(Int::from(5u8))));
+    assert_eq!(parse_as("5u8", NumberType::Int(IntType::U16)).unwrap_err().to_string(), "mismatched types: expected `u16`, found `u8`");
+    assert_eq!(parse_as("5", NumberType::F64).unwrap_err().to_string(), "mismatched types: expected `f64`, found integer");
+    assert_eq!(parse_as("5.0", NumberType::Int(IntType::I32)).unwrap_err().to_string(), "mismatched types: expected `i32`, found floating-point number");
+}
+
+#[test]
+fn accepts_the_grammar_of_rust() {
+    let cases: [(&str, Number); 12] = [
+        ("0b_1010", Number::Int(Int::from(0b_1010))),
+        ("0o777", Number::Int(Int::from(0o777))),
+        ("0xdead_BEEFu32", Number::Int(Int::from(0xdead_beefu32))),
+        ("0x1f32", Number::Int(Int::from(0x1f32))),
+        ("1_2_3", Number::Int(Int::from(1_2_3))),
+        ("123_usize", Number::Int(Int::from(123_usize))),
+        ("-9_223_372_036_854_775_808i64", Number::Int(Int::from(i64::MIN))),
+        ("1_000.000_1", Number::F64(1_000.000_1)),
+        ("2.5E-3", Number::F64(2.5E-3)),
+        ("1e+1_0f64", Number::F64(1e+1_0f64)),
+        ("6.02e23_f32", Number::F32(6.02e23_f32)),
+        (" 42 ", Number::Int(Int::from(42))),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(parse(text), Ok(expected), "{}", text);
+    }
+}
+
+#[test]
+fn round_trips_every_type() {
+    let samples = [0i128, 1, -1, 42, 127, -128, 255, 65_535, i64::MIN as i128, u64::MAX as i128, i128::MAX, i128::MIN];
+    for int_type in INT_TYPES {
+        for value in samples {
+            if let Some(int) = Int::new(int_type, value) {
+                let text = format!("{}{}", value, int_type);
+                assert_eq!(parse(&text).map(integer), Ok(value), "{}", text);
+                assert_eq!(parse(&Number::Int(int).to_string()), Ok(Number::Int(int)));
+            }
+        }
+    }
+
+    for float in [0.1f64, -2.5, 1e-300, f64::MAX, f64::MIN_POSITIVE, 123456.789] {
+        assert_eq!(parse(&Number::F64(float).to_string()), Ok(Number::F64(float)));
+        assert_eq!(parse(&format!("{:e}", float)), Ok(Number::F64(float)));
+    }
+    for float in [0.1f32, f32::MAX, 65.4321] {
+        assert_eq!(parse(&Number::F32(float).to_string()), Ok(Number::F32(float)));
+    }
+}
+
+#[test]
+fn reports_overflowing_literals_with_their_wrapped_value() {
+    let wrapped = |text: &str| match parse(text) {
+        Err(Error::OutOfRange { wrapped, .. }) => wrapped,
+        result => panic!("{} parsed as {:?}", text, result),
+    };
+
+    assert_eq!(wrapped("256u8"), Number::Int(Int::from(256u16 as u8)));
+    assert_eq!(wrapped("128i8"), Number::Int(Int::from(128u8 as i8)));
+    assert_eq!(wrapped("0xffi8"), Number::Int(Int::from(-1i8)));
+    assert_eq!(wrapped("-129i8"), Number::Int(Int::from(-129i16 as i8)));
+    assert_eq!(wrapped("4294967296"), Number::Int(Int::from(4294967296i64 as i32)));
+    assert_eq!(wrapped("1e40f32"), Number::F32(f32::INFINITY));
+    assert_eq!(wrapped("-1e309"), Number::F64(f64::NEG_INFINITY));
+
+    assert_eq!(
+        parse("256u8").unwrap_err().to_string(),
+        "literal out of range for `u8`: the literal `256u8` does not fit into the type `u8` whose range is `0..=255`"
+    );
+    assert_eq!(
+        parse("1e40f32").unwrap_err().to_string(),
+        "literal out of range for `f32`: the literal `1e40f32` does not fit into the type `f32` and will be converted to `f32::INFINITY`"
+    );
+}
+
+#[test]
+fn reports_malformed_literals() {
+    let error = |text: &str| parse(text).unwrap_err().to_string();
+
+    assert_eq!(error("5u7"), "column 2: invalid suffix `u7` for number literal");
+    assert_eq!(error("1.5u8"), "column 4: invalid suffix `u8` for float literal");
+    assert_eq!(error("0b102"), "column 5: `2` is not a digit in base 2");
+    assert_eq!(error("0b1f32"), "column 4: a float suffix like `f32` is only allowed on decimal literals");
+    assert_eq!(error("1.x"), "column 3: expected digits after `.`");
+    assert_eq!(error("1e"), "column 2: expected at least one digit in exponent");
+    assert_eq!(error("_1"), "column 1: `_1` is not a number");
+    assert_eq!(error("0x"), "column 3: expected digits");
+    assert_eq!(error("-5u8"), "cannot apply unary operator `-` to type `u8`");
+    assert_eq!(error("340282366920938463463374607431768211456u128"), "column 1: integer literal is too large");
+    assert_eq!(error(""), "column 1: expected digits");
+}
+
+#[test]
+fn converts_into_primitives() {
+    let port: u16 = parse_as("8_080", NumberType::Int(IntType::U16)).unwrap().try_into().unwrap();
+    assert_eq!(port, 8080);
+    let ratio: f32 = parse("0.75f32").unwrap().try_into().unwrap();
+    assert_eq!(ratio, 0.75);
+    let negative: i8 = parse("-128i8").unwrap().try_into().unwrap();
+    assert_eq!(negative, -128);
+
+    let mismatch = u8::try_from(parse("7").unwrap()).unwrap_err();
+    assert_eq!(mismatch.to_string(), "mismatched types: expected `u8`, found `i32`");
+    assert!(f64::try_from(parse("1f32").unwrap()).is_err());
+}
@@ -0,0 +1,97 @@
+use std::fs;
+use std::time::{Duration, UNIX_EPOCH};
+
+use rust_by_example::log::{self, FileSink, Level, Logger, Memory, Record};
+use rust_by_example::{output, registry, runner};
+
+fn record(level: Level, target: &str, message: &str) -> Record {
+    Record { level, target: target.to_owned(), time: UNIX_EPOCH + Duration::from_millis(1_792_315_800_123), message: message.to_owned() }
+}
+
+#[test]
+fn chapters_log_under_their_name() {
+    let memory = Memory::new();
+    let entry = registry::select("hello_world::format_print")[0];
+
+    let ((), captured) = log::install(Logger::new().sink(memory.clone()), || output::capture(entry.section.run));
+
+    assert_eq!(captured.stderr(), "");
+    let records = memory.records();
+    assert_eq!(records.len(), 1);
+    assert_eq!((records[0].level, records[0].target.as_str()), (Level::Error, "hello_world"));
+    assert_eq!(records[0].message, "Error print");
+}
+
+#[test]
+fn the_runner_logs_sections_at_debug_and_trace() {
+    let memory = Memory::new();
+    let entry = registry::select("expressions::expressions")[0];
+
+    let logger = Logger::new().level(Some(Level::Trace)).sink(memory.clone());
+    log::install(logger, || runner::run_isolated(&entry));
+
+    let messages = memory.messages();
+    assert_eq!(messages[0], "running expressions::expressions");
+    assert!(messages[1].starts_with("expressions::expressions ran in "), "{:?}", messages);
+    assert!(memory.records().iter().all(|record| record.target == "runner"));
+
+    let quiet = Memory::new();
+    log::install(Logger::new().sink(quiet.clone()), || runner::run_isolated(&entry));
+    assert!(quiet.records().is_empty());
+}
+
+#[test]
+fn filters_by_level_and_target() {
+    let logger = Logger::new().filters("warn, hello_world=trace, runner=off").unwrap();
+
+    assert!(logger.enabled(Level::Error, "types"));
+    assert!(logger.enabled(Level::Warn, "types"));
+    assert!(!logger.enabled(Level::Info, "types"));
+    assert!(logger.enabled(Level::Trace, "hello_world"));
+    assert!(!logger.enabled(Level::Error, "runner"));
+
+    let off = Logger::new().filters("off").unwrap();
+    assert!(!off.enabled(Level::Error, "types"));
+    assert!(Logger::new().enabled(Level::Info, "types"));
+    assert!(!Logger::new().enabled(Level::Debug, "types"));
+
+    let error = Logger::new().filters("hello_world=loud").err().unwrap();
+    assert_eq!(error, "unknown log level `loud`, expected `error`, `warn`, `info`, `debug`, `trace` or `off`");
+}
+
+#[test]
+fn formats_records_with_and_without_timestamps() {
+    let record = record(Level::Warn, "generics", "careful");
+    assert_eq!(record.format(false), "[WARN generics] careful");
+    assert_eq!(record.format(true), "[2026-10-18T09:30:00.123Z WARN generics] careful");
+
+    assert_eq!(log::timestamp(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+    assert_eq!(log::timestamp(UNIX_EPOCH + Duration::from_secs(951_782_400)), "2000-02-29T00:00:00.000Z");
+}
+
+#[test]
+fn derives_targets_from_module_paths() {
+    assert_eq!(log::target("rust_by_example::a_hello_world"), "hello_world");
+    assert_eq!(log::target("rust_by_example::j_modules::my_mod::nested"), "modules");
+    assert_eq!(log::target("rust_by_example::runner"), "runner");
+    assert_eq!(log::target("main"), "main");
+}
+
+#[test]
+fn appends_to_a_file() {
+    let path = std::env::temp_dir().join(format!("rust-by-example-log-{}.txt", std::process::id()));
+    let _ = fs::remove_file(&path);
+
+    for message in ["first", "second"] {
+        let mut logger = Logger::new().timestamps(true).sink(FileSink::open(&path).unwrap());
+        logger.log(&record(Level::Info, "runner", message));
+        logger.log(&record(Level::Debug, "runner", "filtered out"));
+    }
+
+    let written = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        written,
+        "[2026-10-18T09:30:00.123Z INFO runner] first\n[2026-10-18T09:30:00.123Z INFO runner] second\n"
+    );
+}
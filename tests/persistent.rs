@@ -0,0 +1,108 @@
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use rust_by_example::persistent::{rc, sync};
+
+#[test]
+fn prepending_shares_the_old_list() {
+    let base = rc::List::new().prepend(1);
+    let two = base.prepend(2);
+    let three = base.prepend(3);
+
+    assert_eq!(two.to_string(), "2, 1, Nil");
+    assert_eq!(three.to_string(), "3, 1, Nil");
+    assert_eq!(base.to_string(), "1, Nil");
+    assert!(two.tail().ptr_eq(&base));
+    assert!(three.tail().ptr_eq(&two.tail()));
+    assert!(!two.ptr_eq(&three));
+
+    drop(base);
+    assert_eq!(two.tail().head(), Some(&1));
+}
+
+#[test]
+fn head_tail_and_iteration() {
+    let list: rc::List<&str> = ["undo", "redo", "save"].into_iter().collect();
+    assert_eq!(list.head(), Some(&"undo"));
+    assert_eq!(list.tail().head(), Some(&"redo"));
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), ["undo", "redo", "save"]);
+    assert_eq!(format!("{:?}", list), r#"["undo", "redo", "save"]"#);
+
+    let empty: rc::List<i32> = rc::List::new();
+    assert!(empty.is_empty());
+    assert_eq!(empty.head(), None);
+    assert!(empty.tail().is_empty());
+    assert_eq!(empty.to_string(), "Nil");
+    assert_eq!(list.clone(), list);
+    assert_ne!(list.tail(), list);
+}
+
+#[test]
+fn keeps_many_versions_of_a_history() {
+    let mut versions = vec![rc::List::new()];
+    for edit in 0..100 {
+        let next = versions.last().unwrap().prepend(edit);
+        versions.push(next);
+    }
+    let undone = versions[100].tail().tail();
+    assert!(undone.ptr_eq(&versions[98]));
+    assert_eq!(undone.head(), Some(&97));
+    assert_eq!(versions.iter().map(rc::List::len).sum::<usize>(), (0..=100).sum());
+}
+
+#[test]
+fn sync_lists_are_shared_between_threads() {
+    let base: sync::List<u64> = (1..=3).collect();
+    let handles: Vec<_> = (10..14)
+        .map(|elem| {
+            let base = base.clone();
+            thread::spawn(move || {
+                let list = base.prepend(elem);
+                assert!(list.tail().ptr_eq(&base));
+                list
+            })
+        })
+        .collect();
+    for (elem, handle) in (10..14).zip(handles) {
+        let list = handle.join().unwrap();
+        assert_eq!(list.to_string(), format!("{}, 1, 2, 3, Nil", elem));
+        assert!(list.tail().ptr_eq(&base));
+    }
+}
+
+#[test]
+fn dropping_long_lists_does_not_overflow_the_stack() {
+    let mut list = rc::List::new();
+    for elem in 0..1_000_000 {
+        list = list.prepend(elem);
+    }
+    let shared = list.tail();
+    drop(list);
+    assert_eq!(shared.len(), 999_999);
+    drop(shared);
+
+    let list: sync::List<u32> = (0..1_000_000).collect();
+    assert_eq!(list.len(), 1_000_000);
+}
+
+#[test]
+fn lists_dropped_at_once_on_several_threads_do_not_overflow_the_stack() {
+    for _ in 0..8 {
+        let list: sync::List<u32> = (0..200_000).collect();
+        let barrier = Arc::new(Barrier::new(2));
+        let handles: Vec<_> = [list.clone(), list]
+            .into_iter()
+            .map(|list| {
+                let barrier = Arc::clone(&barrier);
+                thread::spawn(move || {
+                    barrier.wait();
+                    drop(list);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
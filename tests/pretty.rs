@@ -0,0 +1,164 @@
+#![allow(dead_code)]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Debug;
+
+use rust_by_example::a_hello_world::Person;
+use rust_by_example::c_custom_types::LinkedList;
+use rust_by_example::pretty::{parse, Mode, Node, Pretty, MAX_DEPTH};
+
+#[derive(Debug)]
+struct Unit;
+
+#[derive(Debug)]
+struct Wrapper(i32, &'static str);
+
+#[derive(Debug)]
+enum Shape {
+    Circle { radius: f64 },
+    Point,
+    Polygon(Vec<(i32, i32)>),
+}
+
+struct Partial {
+    shown: u8,
+}
+
+impl Debug for Partial {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Partial").field("shown", &self.shown).finish_non_exhaustive()
+    }
+}
+
+/*
+Both layouts reproduce what the standard library prints when nothing limits
+them, which checks the parser against every shape `Debug` derives.
+ */
+fn check<T: Debug>(value: T) {
+    let wide = Pretty::new().width(usize::MAX);
+    assert_eq!(wide.render(&value), format!("{:?}", value));
+    assert_eq!(Pretty::new().mode(Mode::Expanded).render(&value), format!("{:#?}", value));
+}
+
+#[test]
+fn reproduces_debug_and_alternate_debug() {
+    check(Person { name: "Peter", age: 27 });
+    check(((1u8, 2u16, 2u32), (4u64, -1i8), -2i16));
+    check((5u32,));
+    check(());
+    check(Unit);
+    check(Wrapper(-3, "with \"quotes\", commas and a } brace"));
+    check(vec![Shape::Circle { radius: 1.5 }, Shape::Point, Shape::Polygon(vec![(0, 0), (3, -4)])]);
+    check(Some(vec!['a', '\'', '\n']));
+    check(BTreeMap::from([("one", vec![1.0, -2e-7]), ("two", vec![])]));
+    check(BTreeSet::from([3, 1, 2]));
+    check(Partial { shown: 7 });
+    check(0..5);
+    check(Vec::<Vec<i32>>::new());
+}
+
+#[test]
+fn compact_mode_breaks_only_what_does_not_fit() {
+    let value = vec![
+        Person { name: "Peter", age: 27 },
+        Person { name: "Alexandra", age: 31 },
+    ];
+    assert_eq!(
+        Pretty::new().width(42).render(&value),
+        "[\n    Person { name: \"Peter\", age: 27 },\n    Person { name: \"Alexandra\", age: 31 },\n]"
+    );
+    assert_eq!(
+        Pretty::new().width(38).render(&value),
+        concat!(
+            "[\n",
+            "    Person { name: \"Peter\", age: 27 },\n",
+            "    Person {\n",
+            "        name: \"Alexandra\",\n",
+            "        age: 31,\n",
+            "    },\n",
+            "]"
+        )
+    );
+
+    let tuple_of_tuples = ((1u8, 2u16, 2u32), (4u64, -1i8), -2i16);
+    assert_eq!(Pretty::new().width(80).render(&tuple_of_tuples), "((1, 2, 2), (4, -1), -2)");
+    assert_eq!(Pretty::new().width(20).render(&tuple_of_tuples), "(\n    (1, 2, 2),\n    (4, -1),\n    -2,\n)");
+}
+
+#[test]
+fn elides_values_deeper_than_the_limit() {
+    let value = vec![Shape::Circle { radius: 1.0 }, Shape::Polygon(vec![(1, 2)]), Shape::Point];
+
+    assert_eq!(Pretty::new().depth(0).render(&value), "[..]");
+    assert_eq!(Pretty::new().depth(1).render(&value), "[Circle { .. }, Polygon(..), Point]");
+    assert_eq!(Pretty::new().depth(2).render(&value), "[Circle { radius: 1.0 }, Polygon([..]), Point]");
+    assert_eq!(Pretty::new().depth(1).mode(Mode::Expanded).render(&value), "[\n    Circle { .. },\n    Polygon(..),\n    Point,\n]");
+    assert_eq!(Pretty::new().depth(0).render(&Vec::<u8>::new()), "[]");
+}
+
+#[test]
+fn colors_atoms_and_names() {
+    let colored = Pretty::new().color(true).render(&Person { name: "Peter", age: 27 });
+    assert_eq!(colored, "\x1b[36mPerson\x1b[0m { name: \x1b[32m\"Peter\"\x1b[0m, age: \x1b[33m27\x1b[0m }");
+
+    /*
+    The escapes take no room when deciding what fits.
+     */
+    let plain = Pretty::new().width(33).render(&Person { name: "Peter", age: 27 });
+    assert_eq!(plain.lines().count(), 1);
+    assert_eq!(Pretty::new().width(33).color(true).render(&Person { name: "Peter", age: 27 }).lines().count(), 1);
+}
+
+#[test]
+fn parses_into_a_tree() {
+    assert_eq!(
+        parse("Some(Person { name: \"Peter\", age: 27 })").unwrap(),
+        Node::Tuple {
+            name: Some("Some".to_owned()),
+            items: vec![Node::Struct {
+                name: "Person".to_owned(),
+                fields: vec![
+                    ("name".to_owned(), Node::Atom("\"Peter\"".to_owned())),
+                    ("age".to_owned(), Node::Atom("27".to_owned())),
+                ],
+                non_exhaustive: false,
+            }],
+        }
+    );
+    assert_eq!(parse("{1: a::B}").unwrap(), Node::Map(vec![(Node::Atom("1".to_owned()), Node::Atom("a::B".to_owned()))]));
+}
+
+#[test]
+fn falls_back_to_debug_for_free_text() {
+    struct Free;
+    impl Debug for Free {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "not [ a value")
+        }
+    }
+
+    assert_eq!(Pretty::new().render(&Free), "not [ a value");
+    let error = parse("[1, 2").unwrap_err();
+    assert_eq!(error.to_string(), "column 6: expected `,` or `]`, found the end");
+    assert_eq!(parse("\"open").unwrap_err().to_string(), "column 1: unterminated `\"`");
+}
+
+#[test]
+fn deep_values_are_elided_or_printed_as_debug() {
+    let list = (0..20_000).fold(LinkedList::new(), LinkedList::prepend);
+    let debug = format!("{:?}", list);
+
+    assert_eq!(Pretty::new().render(&list), debug);
+    assert_eq!(Pretty::new().mode(Mode::Expanded).render(&list), format!("{:#?}", list));
+    assert_eq!(Pretty::new().depth(2).render(&list), "Node(19999, Node(19998, Node(..)))");
+    assert_eq!(Pretty::new().depth(1).mode(Mode::Expanded).render(&list), "Node(\n    19999,\n    Node(..),\n)");
+
+    let nested = |depth: usize| format!("{}{}", "[".repeat(depth), "]".repeat(depth));
+    assert!(parse(&nested(MAX_DEPTH)).is_ok());
+    assert_eq!(
+        parse(&nested(MAX_DEPTH + 1)).unwrap_err().to_string(),
+        format!("column {}: nesting too deep", MAX_DEPTH + 1)
+    );
+    assert_eq!(Pretty::new().depth(1).render(&[vec![(); 0]]), "[[]]");
+    assert_eq!(Pretty::new().depth(0).render(&Person { name: "}", age: 1 }), "Person { .. }");
+}
@@ -0,0 +1,60 @@
+use std::fs;
+use std::io::Cursor;
+
+use rust_by_example::output::Output;
+use rust_by_example::repl::{self, Repl};
+
+fn session(name: &str, input: &str) -> String {
+    let path = std::env::temp_dir().join(format!("rust-by-example-{}-{}.txt", std::process::id(), name));
+
+    repl::run(Cursor::new(input.to_owned()), Output::file(&path).unwrap()).unwrap();
+
+    let transcript = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    transcript
+}
+
+#[test]
+fn moves_between_sections_across_chapters() {
+    let mut repl = Repl::new();
+    let mut output = Output::new(std::io::sink(), std::io::sink());
+
+    assert_eq!(repl.prompt(), "hello_world::intro> ");
+    repl.eval("prev", &mut output).unwrap();
+    assert_eq!(repl.current().path(), "hello_world::intro");
+
+    repl.eval("go hello_world::formatting", &mut output).unwrap();
+    repl.eval("next", &mut output).unwrap();
+    assert_eq!(repl.current().path(), "primitives::types");
+
+    repl.eval("go scoping_rules::lifetime*", &mut output).unwrap();
+    assert_eq!(repl.current().path(), "scoping_rules::lifetime");
+    assert!(!repl.eval("quit", &mut output).unwrap());
+}
+
+#[test]
+fn runs_reruns_and_diffs_the_current_section() {
+    let transcript = session("run", "go expressions::expressions\nrun\nrerun\ndiff\nbogus\n");
+
+    assert_eq!(
+        transcript,
+        "Rust by Example, type `help` for the commands.\n\
+         hello_world::intro> Expressions (Expressions, section 1 of 1)\n\
+         expressions::expressions> x is 5\ny is 155\nz is ()\n\
+         expressions::expressions> x is 5\ny is 155\nz is ()\n\
+         expressions::expressions> expected output, in order:\n    \
+         ok      x is 5\n    ok      y is 155\n    ok      z is ()\n\
+         actual output:\n    | x is 5\n    | y is 155\n    | z is ()\n\
+         3 of 3 expected lines found\n\
+         expressions::expressions> error: unknown command `bogus`, see `help`\n\
+         expressions::expressions> \n"
+    );
+}
+
+#[test]
+fn reports_sections_that_panic() {
+    let transcript = session("panic", "run functions::calling_diverging_functions\nquit\n");
+
+    assert!(transcript.contains("Calling `foo()`\nsection `functions::calling_diverging_functions` panicked 'This call never returns.'"));
+    assert!(transcript.ends_with("functions::calling_diverging_functions> "));
+}
@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use rust_by_example::golden::Verdict;
+use rust_by_example::json::Value;
+use rust_by_example::output::{self, Stream};
+use rust_by_example::registry;
+use rust_by_example::report::{escape_xml, ChapterReport, Report, SectionReport};
+use rust_by_example::runner::{Outcome, Panic};
+
+fn section(path: &str, stdout: &str, expected: &[&str], verdict: Verdict) -> SectionReport {
+    let entry = registry::select(path)[0];
+    let ((), captured) = output::capture(|| output::print(Stream::Stdout, format_args!("{}", stdout)));
+    let panic = match &verdict {
+        Verdict::Panicked(_) => Some(Panic { message: "<boom> & \"bang\"".to_owned(), location: Some("src/a.rs:1:2".to_owned()) }),
+        _ => None,
+    };
+    SectionReport {
+        entry,
+        outcome: Outcome { captured, panic, duration: Duration::ZERO },
+        expected: expected.iter().map(|line| line.to_string()).collect(),
+        verdict,
+    }
+}
+
+fn report() -> Report {
+    let hello_world = registry::find("hello_world").unwrap();
+    let rust = registry::find("rust").unwrap();
+    Report {
+        chapters: vec![
+            ChapterReport {
+                chapter: hello_world,
+                sections: vec![
+                    section("hello_world::intro", "Hello world!\n", &["Hello world!"], Verdict::Pass),
+                    section("hello_world::debug", "a > b\n", &["a < b && 'c'", "\"d\""], Verdict::Mismatch { missing: vec![0, 1] }),
+                ],
+            },
+            ChapterReport {
+                chapter: rust,
+                sections: vec![
+                    section("rust::casting", "", &[], Verdict::Panicked("'<boom> & \"bang\"' at src/a.rs:1:2".to_owned())),
+                    section("rust::literals", "", &[], Verdict::Skipped("output depends on the target platform")),
+                    section("rust::inference", "", &[], Verdict::Unannotated),
+                ],
+            },
+        ],
+    }
+}
+
+#[test]
+fn json_has_a_summary_and_every_section() {
+    let json = report().to_json();
+    let summary = json.get("summary").unwrap();
+    assert_eq!(summary.get("passed"), Some(&Value::Number(1.0)));
+    assert_eq!(summary.get("failed"), Some(&Value::Number(2.0)));
+    assert_eq!(summary.get("unannotated"), Some(&Value::Number(1.0)));
+    assert_eq!(summary.get("skipped"), Some(&Value::Number(1.0)));
+
+    let Some(Value::Array(chapters)) = json.get("chapters") else { panic!("no chapters in {}", json) };
+    assert_eq!(chapters.len(), 2);
+    assert_eq!(chapters[0].get("name"), Some(&Value::from("hello_world")));
+    assert_eq!(chapters[0].get("module"), Some(&Value::from("a_hello_world")));
+    assert_eq!(chapters[1].get("summary").and_then(|summary| summary.get("failed")), Some(&Value::Number(1.0)));
+
+    let Some(Value::Array(sections)) = chapters[0].get("sections") else { panic!("no sections in {}", chapters[0]) };
+    let keys = |value: &Value| match value {
+        Value::Object(entries) => entries.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>(),
+        _ => Vec::new(),
+    };
+    assert_eq!(
+        keys(&sections[1]),
+        ["name", "path", "title", "status", "duration_ms", "stdout", "stderr", "expected", "missing", "panic"]
+    );
+    assert_eq!(sections[1].get("path"), Some(&Value::from("hello_world::debug")));
+    assert_eq!(sections[1].get("status"), Some(&Value::from("fail")));
+    assert_eq!(sections[1].get("stdout"), Some(&Value::from("a > b\n")));
+    assert_eq!(sections[1].get("missing"), Some(&Value::from(vec!["a < b && 'c'", "\"d\""])));
+    assert_eq!(sections[0].get("panic"), Some(&Value::Null));
+
+    let Some(Value::Array(sections)) = chapters[1].get("sections") else { panic!("no sections in {}", chapters[1]) };
+    let statuses: Vec<_> = sections.iter().filter_map(|section| section.get("status")).collect();
+    assert_eq!(statuses, [&Value::from("panic"), &Value::from("skipped"), &Value::from("unannotated")]);
+    assert_eq!(
+        sections[0].get("panic"),
+        Some(&Value::object([("message", Value::from("<boom> & \"bang\"")), ("location", Value::from("src/a.rs:1:2"))]))
+    );
+}
+
+#[test]
+fn junit_has_a_suite_per_chapter_and_a_case_per_section() {
+    assert_eq!(
+        report().to_junit(),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="rust-by-example" tests="5" failures="2" skipped="1" time="0.000000">
+  <testsuite name="hello_world" tests="2" failures="1" skipped="0" time="0.000000">
+    <testcase classname="hello_world" name="intro" time="0.000000">
+      <system-out>Hello world!
+</system-out>
+    </testcase>
+    <testcase classname="hello_world" name="debug" time="0.000000">
+      <failure message="output does not match the annotated output">missing, in order:
+a &lt; b &amp;&amp; &apos;c&apos;
+&quot;d&quot;</failure>
+      <system-out>a &gt; b
+</system-out>
+    </testcase>
+  </testsuite>
+  <testsuite name="rust" tests="3" failures="1" skipped="1" time="0.000000">
+    <testcase classname="rust" name="casting" time="0.000000">
+      <error message="panicked &apos;&lt;boom&gt; &amp; &quot;bang&quot;&apos; at src/a.rs:1:2" type="panic"/>
+    </testcase>
+    <testcase classname="rust" name="literals" time="0.000000">
+      <skipped message="output depends on the target platform"/>
+    </testcase>
+    <testcase classname="rust" name="inference" time="0.000000"/>
+  </testsuite>
+</testsuites>
+"#
+    );
+}
+
+#[test]
+fn xml_escapes_markup_and_drops_control_characters() {
+    assert_eq!(escape_xml("<a href=\"x\">'&'</a>"), "&lt;a href=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;");
+    assert_eq!(escape_xml("bell\u{7}\ttab\nline"), "bell\ttab\nline");
+}
@@ -0,0 +1,82 @@
+use rust_by_example::a_hello_world::{City, Color};
+use rust_by_example::table::{display_width, Align, Column, Style, Table};
+
+fn cities() -> Vec<City> {
+    vec![
+        City { name: "Dublin", lat: 53.34778, lon: -6.25972 },
+        City { name: "Oslo", lat: 59.95, lon: 10.75 },
+        City { name: "Vancouver", lat: 49.25, lon: -123.1 },
+    ]
+}
+
+fn city_table<'a>() -> Table<'a, City> {
+    Table::new()
+        .with(Column::new("city", |city: &City| city.name))
+        .with(Column::new("lat", |city: &City| city.lat).precision(3))
+        .with(Column::new("lon", |city: &City| city.lon).precision(3))
+        .with(Column::new("display", |city: &City| city.to_string()))
+}
+
+#[test]
+fn plain_tables_align_by_display_width() {
+    assert_eq!(
+        city_table().render(&cities(), Style::Plain),
+        "\
+city          lat       lon  display
+---------  ------  --------  -----------------------------
+Dublin     53.348    -6.260  Dublin: 53.348°N 6.260°W
+Oslo       59.950    10.750  Oslo: 59.950°N 10.750°E
+Vancouver  49.250  -123.100  Vancouver: 49.250°N 123.100°W
+"
+    );
+}
+
+#[test]
+fn markdown_tables_mark_the_alignment_and_escape_pipes() {
+    let colors = [Color { red: 128, green: 255, blue: 90 }, Color { red: 0, green: 3, blue: 254 }];
+    let table = Table::new()
+        .with(Column::new("red", |color: &Color| color.red))
+        .with(Column::new("hex", |color: &Color| format!("{:02X}|{:02X}|{:02X}", color.red, color.green, color.blue)))
+        .with(Column::new("rgb", |color: &Color| color.to_string()).align(Align::Center));
+
+    assert_eq!(
+        table.render(&colors, Style::Markdown),
+        "\
+| red | hex        |             rgb             |
+| --: | :--------- | :-------------------------: |
+| 128 | 80\\|FF\\|5A | RGB (128, 255, 90) 0x80FF5A |
+|   0 | 00\\|03\\|FE |  RGB (0, 3, 254) 0x0003FE   |
+"
+    );
+}
+
+#[test]
+fn csv_quotes_fields_that_need_it() {
+    let rows = [("plain", 1.5), ("comma, here", -2.0), ("a \"quote\"", 0.25), (" padded", 3.0)];
+    let table = Table::new()
+        .with(Column::new("text", |row: &(&str, f64)| row.0))
+        .with(Column::new("value", |row: &(&str, f64)| row.1).precision(1));
+
+    assert_eq!(
+        table.render(&rows, Style::Csv),
+        "text,value\nplain,1.5\n\"comma, here\",-2.0\n\"a \"\"quote\"\"\",0.2\n\" padded\",3.0\n"
+    );
+}
+
+#[test]
+fn padding_widens_the_gaps() {
+    let rows = [1, 22];
+    let table = Table::new()
+        .with(Column::new("n", |n: &i32| *n))
+        .with(Column::new("square", |n: &i32| n * n))
+        .padding(2);
+
+    assert_eq!(table.render(&rows, Style::Plain), " n    square\n--    ------\n 1         1\n22       484\n");
+}
+
+#[test]
+fn display_width_counts_terminal_columns() {
+    assert_eq!(display_width("53.348°N"), 8);
+    assert_eq!(display_width("東京"), 4);
+    assert_eq!(display_width("e\u{301}"), 1);
+}
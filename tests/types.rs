@@ -0,0 +1,9 @@
+use rust_by_example::e_rust::{Inch, NanoSecond, U64};
+
+#[test]
+fn aliases_are_the_same_type() {
+    let nanoseconds: NanoSecond = 5 as U64;
+    let inches: Inch = 2 as U64;
+
+    assert_eq!(nanoseconds + inches, 7);
+}
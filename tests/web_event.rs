@@ -0,0 +1,129 @@
+use rust_by_example::c_custom_types::{inspect, WebEvent};
+use rust_by_example::output;
+use rust_by_example::web_event::{self, from_text, read_json, read_text, to_json, write_json, write_text, Error};
+
+fn events() -> Vec<WebEvent> {
+    vec![
+        WebEvent::PageLoad,
+        WebEvent::KeyPress('x'),
+        WebEvent::Paste("my text".to_owned()),
+        WebEvent::Click { x: 20, y: 80 },
+        WebEvent::PageUnload,
+    ]
+}
+
+fn awkward_events() -> Vec<WebEvent> {
+    vec![
+        WebEvent::KeyPress('\''),
+        WebEvent::KeyPress('"'),
+        WebEvent::KeyPress('\\'),
+        WebEvent::KeyPress('\n'),
+        WebEvent::KeyPress('\u{7}'),
+        WebEvent::KeyPress('é'),
+        WebEvent::Paste(String::new()),
+        WebEvent::Paste("say \"hi\"\nand 'bye'\t\\o/ 🦀\r\0".to_owned()),
+        WebEvent::Paste("\"clicked at x=1, y=2.\"".to_owned()),
+        WebEvent::Click { x: -5, y: 0 },
+        WebEvent::Click { x: i64::MIN, y: i64::MAX },
+    ]
+}
+
+#[test]
+fn text_matches_what_inspect_prints() {
+    let ((), printed) = output::capture(|| {
+        for event in events() {
+            inspect(event);
+        }
+    });
+    assert_eq!(write_text(&events()), printed.stdout());
+    assert_eq!(read_text(&printed.stdout()), Ok(events()));
+    assert_eq!(WebEvent::Click { x: 20, y: 80 }.to_string(), "clicked at x=20, y=80.");
+}
+
+#[test]
+fn text_round_trips_and_escapes() {
+    for event in events().into_iter().chain(awkward_events()) {
+        let line = web_event::to_text(&event);
+        assert!(!line.contains('\n'), "{:?} spans lines", line);
+        assert_eq!(line.parse::<WebEvent>(), Ok(event), "{}", line);
+    }
+    assert_eq!(web_event::to_text(&WebEvent::KeyPress('\'')), r"pressed '\''.");
+    assert_eq!(web_event::to_text(&WebEvent::Paste("a\"b\nc".to_owned())), r#"pasted "a\"b\nc"."#);
+    assert_eq!(from_text(r"pressed '\u{1F980}'."), Ok(WebEvent::KeyPress('🦀')));
+    assert_eq!(read_text("\npage loaded\r\n\n  \npage unloaded\n"), Ok(vec![WebEvent::PageLoad, WebEvent::PageUnload]));
+}
+
+#[test]
+fn json_round_trips() {
+    assert_eq!(write_json(&WebEvent::Click { x: 20, y: 80 }), r#"{"type":"click","x":20,"y":80}"#);
+    assert_eq!(write_json(&WebEvent::KeyPress('x')), r#"{"type":"key_press","key":"x"}"#);
+    assert_eq!(write_json(&WebEvent::PageLoad), r#"{"type":"page_load"}"#);
+    assert_eq!(
+        write_json(&WebEvent::Click { x: i64::MIN, y: 9_007_199_254_740_992 }),
+        r#"{"type":"click","x":"-9223372036854775808","y":"9007199254740992"}"#
+    );
+    assert_eq!(
+        write_json(&WebEvent::Click { x: -9_007_199_254_740_991, y: 0 }),
+        r#"{"type":"click","x":-9007199254740991,"y":0}"#
+    );
+
+    for event in events().into_iter().chain(awkward_events()) {
+        assert_eq!(read_json(&write_json(&event)), Ok(event.clone()));
+        assert_eq!(read_json(&to_json(&event).pretty()), Ok(event));
+    }
+    assert_eq!(read_json(r#" { "y" : -3 , "x" : 1e2, "type" : "click" } "#), Ok(WebEvent::Click { x: 100, y: -3 }));
+    assert_eq!(read_json(r#"{"type":"paste","text":"🦀 é"}"#), Ok(WebEvent::Paste("🦀 é".to_owned())));
+}
+
+#[test]
+fn reports_malformed_text() {
+    let error = |line: &str| from_text(line).unwrap_err().to_string();
+
+    assert_eq!(
+        error("page reloaded"),
+        "line 1, column 1: expected an event, like `page loaded`, `page unloaded`, `pressed 'x'.`, `pasted \"my text\".` or `clicked at x=20, y=80.`"
+    );
+    assert_eq!(error("page loaded!"), "line 1, column 12: unexpected `!` after the event");
+    assert_eq!(error("pressed 'x'"), "line 1, column 12: expected `.`, found the end of the line");
+    assert_eq!(error("pressed 'xy'."), "line 1, column 11: expected `'`, found `y`");
+    assert_eq!(error("pressed ''."), "line 1, column 10: expected a key between the quotes");
+    assert_eq!(error("pressed x."), "line 1, column 9: expected `'`, found `x`");
+    assert_eq!(error(r"pressed '\q'."), r"line 1, column 11: expected an escape like `\n`, `\\` or `\u{e9}`, found `q`");
+    assert_eq!(error(r"pressed '\u{d800}'."), r"line 1, column 10: `\u{d800}` is not a character");
+    assert_eq!(error("pasted \"open."), "line 1, column 14: expected a closing `\"`");
+    assert_eq!(error("clicked at x=20 y=80."), "line 1, column 16: expected `, y=`, found ` `");
+    assert_eq!(error("clicked at x=, y=80."), "line 1, column 14: expected an integer, found `,`");
+    assert_eq!(error("clicked at x=99999999999999999999, y=0."), "line 1, column 14: `99999999999999999999` does not fit into `i64`");
+    assert_eq!(
+        read_text("page loaded\nclicked at x=1, y=2\n").unwrap_err().to_string(),
+        "line 2, column 20: expected `.`, found the end of the line"
+    );
+}
+
+#[test]
+fn reports_malformed_json() {
+    let error = |text: &str| read_json(text).unwrap_err().to_string();
+
+    assert!(matches!(read_json("{\"type\": }"), Err(Error::Json(_))));
+    assert_eq!(error("{\"type\": }"), "line 1, column 10: expected a value, found `}`");
+    assert_eq!(error("{\"type\":\"click\",\n \"x\": 1,\n \"y\": 2,}"), "line 3, column 9: expected a string key, found `}`");
+    assert_eq!(error("[]"), "expected an event object, found an array");
+    assert_eq!(error("{}"), "missing field `type`");
+    assert_eq!(error(r#"{"type":1}"#), "`type` must be a string, found a number");
+    assert_eq!(
+        error(r#"{"type":"scroll"}"#),
+        "unknown event type `scroll`, expected `page_load`, `page_unload`, `key_press`, `paste` or `click`"
+    );
+    assert_eq!(error(r#"{"type":"click","x":1}"#), "missing field `y` for a `click` event");
+    assert_eq!(error(r#"{"type":"click","x":1,"y":2,"z":3}"#), "unknown field `z` for a `click` event");
+    assert_eq!(error(r#"{"type":"click","x":1.5,"y":2}"#), "`x` must be an integer between -9007199254740991 and 9007199254740991, found 1.5");
+    assert_eq!(
+        error(r#"{"type":"click","x":"1","y":2}"#),
+        "`x` must be a number, or a string of an integer beyond -9007199254740991 or 9007199254740991, found \"1\""
+    );
+    assert_eq!(error(r#"{"type":"click","x":1,"y":true}"#), "`y` must be a number, found a boolean");
+    assert_eq!(error(r#"{"type":"key_press","key":"ab"}"#), "`key` must be a string of one character, found a string of 2 characters");
+    assert_eq!(error(r#"{"type":"key_press","key":""}"#), "`key` must be a string of one character, found an empty string");
+    assert_eq!(error(r#"{"type":"key_press","key":["a","b"]}"#), "`key` must be a string of one character, found an array");
+    assert_eq!(error(r#"{"type":"paste","text":null}"#), "`text` must be a string, found null");
+}